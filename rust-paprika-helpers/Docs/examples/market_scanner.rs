@@ -102,7 +102,7 @@ async fn main() -> Result<()> {
     // Example 7: Async Operations
     println!("\n⚡ Async Operations:");
     let networks = vec!["ethereum".to_string(), "polygon".to_string()];
-    match async_get_multiple_pools(&networks, 3).await {
+    match async_get_multiple_pools(&networks, 3, None).await {
         Ok(results) => {
             println!("  Got concurrent data from {} networks", results.len());
             for network in &networks {
@@ -129,7 +129,7 @@ async fn main() -> Result<()> {
     println!("\n🔬 Advanced Analysis:");
     let networks_to_analyze = vec!["ethereum".to_string(), "solana".to_string()];
     
-    match async_get_multiple_pools(&networks_to_analyze, 20).await {
+    match async_get_multiple_pools(&networks_to_analyze, 20, None).await {
         Ok(results) => {
             println!("🔥 Multi-Network Market Scanner");
             println!("{}", "=".repeat(50));
@@ -3,9 +3,14 @@
 //! This module contains all the Rust types that correspond to the DexPaprika API
 //! data structures, with full serde support for JSON serialization/deserialization.
 
-use serde::{Deserialize, Deserializer, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::collections::HashMap;
+use std::str::FromStr;
+use std::time::Duration;
 use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use crate::batch::RetryPolicy;
 
 /// Custom deserializer that converts null to 0.0 for f64 fields
 fn deserialize_f64_from_null<'de, D>(deserializer: D) -> Result<f64, D::Error>
@@ -16,6 +21,61 @@ where
     Ok(opt.unwrap_or(0.0))
 }
 
+/// TokenAmount is a high-precision token amount backed by `Decimal`, avoiding
+/// the rounding error `f64` introduces on large integer token balances and
+/// tiny per-unit prices. Deserializes from either a `"0x..."` hex string, a
+/// plain decimal string, or a JSON number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct TokenAmount(pub Decimal);
+
+impl TokenAmount {
+    /// Lossy `f64` accessor for the existing analysis functions.
+    pub fn as_f64(&self) -> f64 {
+        use rust_decimal::prelude::ToPrimitive;
+        self.0.to_f64().unwrap_or(0.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let value = Value::deserialize(deserializer)?;
+        let decimal = match value {
+            Value::String(s) => parse_hex_or_decimal(&s).map_err(serde::de::Error::custom)?,
+            Value::Number(n) => n
+                .as_f64()
+                .and_then(|f| Decimal::try_from(f).ok())
+                .ok_or_else(|| serde::de::Error::custom("invalid numeric token amount"))?,
+            other => {
+                return Err(serde::de::Error::custom(format!(
+                    "expected a string or number token amount, got {other:?}"
+                )))
+            }
+        };
+        Ok(TokenAmount(decimal))
+    }
+}
+
+impl Serialize for TokenAmount {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+fn parse_hex_or_decimal(s: &str) -> std::result::Result<Decimal, String> {
+    if let Some(hex) = s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        let value = u128::from_str_radix(hex, 16).map_err(|e| e.to_string())?;
+        Ok(Decimal::from(value))
+    } else {
+        Decimal::from_str(s).map_err(|e| e.to_string())
+    }
+}
+
 /// Network represents a blockchain network
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Network {
@@ -196,6 +256,15 @@ pub struct OHLCVRecord {
     pub low: f64,
     pub close: f64,
     pub volume: i64,
+    /// False only for an in-progress candle whose close time is still in the
+    /// future (e.g. the final bucket produced by `aggregate_candles`).
+    /// Records deserialized from the API are always complete.
+    #[serde(default = "default_true")]
+    pub complete: bool,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// Transaction represents a transaction
@@ -214,8 +283,8 @@ pub struct Transaction {
     pub token_0_symbol: String,
     pub token_1: String,
     pub token_1_symbol: String,
-    pub amount_0: String,
-    pub amount_1: String,
+    pub amount_0: TokenAmount,
+    pub amount_1: TokenAmount,
     pub price_0: f64,
     pub price_1: f64,
     pub price_0_usd: f64,
@@ -278,6 +347,17 @@ pub struct AnalysisResult {
     pub timestamp: String,
 }
 
+/// PercentileSummary represents positional quantiles of a value series
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PercentileSummary {
+    pub min: f64,
+    pub median: f64,
+    pub p75: f64,
+    pub p90: f64,
+    pub p95: f64,
+    pub max: f64,
+}
+
 /// AnomalyResult represents detected anomalies
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AnomalyResult {
@@ -315,6 +395,11 @@ pub struct MarketOverview {
     pub system_stats: SystemStats,
     pub network_overview: HashMap<String, serde_json::Value>,
     pub timestamp: String,
+    /// Rough USD cost of a standard AMM swap (`gas::STANDARD_SWAP_GAS_UNITS`)
+    /// at a conservative base-fee/tip assumption, priced off the
+    /// highest-volume Ethereum pool found in `network_overview`. `None` if no
+    /// Ethereum network/pool was present in this overview.
+    pub sample_swap_gas_cost_usd: Option<f64>,
 }
 
 /// AsyncResult represents the result of async operations
@@ -343,6 +428,12 @@ pub struct ApiParams {
     pub cursor: Option<String>,
     pub reorder: Option<bool>,
     pub address: Option<String>,
+    /// Per-request timeout override for `api_request`, falling back to
+    /// `DEFAULT_TIMEOUT` when unset.
+    pub timeout: Option<Duration>,
+    /// Per-request retry/backoff override for `api_request`, falling back to
+    /// `ClientConfig::retry_policy` when unset.
+    pub retry_policy: Option<RetryPolicy>,
 }
 
 impl ApiParams {
@@ -385,6 +476,16 @@ impl ApiParams {
         self
     }
 
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    pub fn retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(retry_policy);
+        self
+    }
+
     /// Convert to URL query parameters
     pub fn to_query_params(&self) -> Vec<(&str, String)> {
         let mut params = Vec::new();
@@ -1,4 +1,5 @@
 use paprika_helpers::*;
+use rust_decimal::Decimal;
 
 #[tokio::test]
 async fn test_get_networks() {
@@ -252,7 +253,7 @@ fn test_top_n() {
         },
     ];
     
-    let top_2 = top_n(&mock_pools, "volume_usd", 2);
+    let top_2 = top_n(&mock_pools, "volume_usd", 2).unwrap();
     assert_eq!(top_2.len(), 2);
     assert_eq!(top_2[0].id, "pool1"); // Highest volume
     assert_eq!(top_2[1].id, "pool2"); // Second highest
@@ -305,7 +306,7 @@ fn test_validate_token_address() {
 #[tokio::test]
 async fn test_async_get_multiple_pools() {
     let networks = vec!["ethereum".to_string(), "polygon".to_string()];
-    let result = async_get_multiple_pools(&networks, 2).await;
+    let result = async_get_multiple_pools(&networks, 2, None).await;
     assert!(result.is_ok());
     
     let results = result.unwrap();
@@ -346,6 +347,7 @@ fn test_calculate_volatility() {
             low: 95.0,
             close: 102.0,
             volume: 1000,
+            complete: true,
         },
         OHLCVRecord {
             time_open: "2023-01-01T01:00:00Z".to_string(),
@@ -355,6 +357,7 @@ fn test_calculate_volatility() {
             low: 98.0,
             close: 105.0,
             volume: 1200,
+            complete: true,
         },
         OHLCVRecord {
             time_open: "2023-01-01T02:00:00Z".to_string(),
@@ -364,6 +367,7 @@ fn test_calculate_volatility() {
             low: 100.0,
             close: 103.0,
             volume: 900,
+            complete: true,
         },
     ];
     
@@ -384,6 +388,531 @@ fn test_calculate_gini_coefficient() {
     println!("✅ Calculated Gini coefficient: {:.4}", gini);
 }
 
+#[test]
+fn test_percentiles() {
+    let values = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+    let summary = percentiles(&values);
+
+    assert_eq!(summary.min, 1.0);
+    assert_eq!(summary.max, 10.0);
+    assert_eq!(summary.median, 6.0);
+
+    println!("✅ Percentile summary: {:?}", summary);
+}
+
+#[test]
+fn test_detect_anomalies_robust_not_swamped_by_whale() {
+    let mut mock_pools = Vec::new();
+    for i in 0..9 {
+        mock_pools.push(Pool {
+            id: format!("pool{}", i),
+            dex_id: "dex".to_string(),
+            dex_name: "DEX".to_string(),
+            chain: "ethereum".to_string(),
+            volume_usd: 1_000_000.0,
+            created_at: "2023-01-01T00:00:00Z".to_string(),
+            created_at_block_number: 123456,
+            transactions: 100,
+            price_usd: 1.0,
+            last_price_change_usd_5m: 0.0,
+            last_price_change_usd_1h: 0.0,
+            last_price_change_usd_24h: 1.0,
+            fee: None,
+            tokens: vec![],
+            last_price: None,
+            last_price_usd: None,
+            price_time: None,
+            h24: None,
+            h6: None,
+            h1: None,
+            m30: None,
+            m15: None,
+            m5: None,
+        });
+    }
+    // One whale pool that would dominate a mean/std-dev z-score.
+    let mut whale = mock_pools[0].clone();
+    whale.id = "whale".to_string();
+    whale.volume_usd = 1_000_000_000.0;
+    mock_pools.push(whale);
+
+    let anomalies = detect_anomalies_robust(&mock_pools, "volume_usd", 3.5);
+    assert_eq!(anomalies.len(), 1);
+    assert_eq!(anomalies[0].index, 9);
+
+    println!("✅ Robust anomaly detection found {} anomalies", anomalies.len());
+}
+
+#[test]
+fn test_aggregate_candles_fills_gaps() {
+    let records = vec![
+        OHLCVRecord {
+            time_open: "2023-01-01T00:00:00Z".to_string(),
+            time_close: "2023-01-01T00:01:00Z".to_string(),
+            open: 100.0,
+            high: 110.0,
+            low: 95.0,
+            close: 105.0,
+            volume: 10,
+            complete: true,
+        },
+        OHLCVRecord {
+            time_open: "2023-01-01T00:03:30Z".to_string(),
+            time_close: "2023-01-01T00:04:00Z".to_string(),
+            open: 106.0,
+            high: 108.0,
+            low: 104.0,
+            close: 107.0,
+            volume: 5,
+            complete: true,
+        },
+    ];
+
+    let candles = aggregate_candles(&records, 60);
+
+    // Buckets at :00, :01, :02, :03 (gap-filled) should all be present.
+    assert_eq!(candles.len(), 4);
+    assert_eq!(candles[0].close, 105.0);
+    // Gap-filled buckets carry the previous close with zero volume.
+    assert_eq!(candles[1].open, 105.0);
+    assert_eq!(candles[1].volume, 0);
+    assert_eq!(candles[2].close, 105.0);
+    assert_eq!(candles[3].close, 107.0);
+
+    println!("✅ Aggregated {} candles with gaps filled", candles.len());
+}
+
+#[test]
+fn test_write_coingecko_tickers_json() {
+    let pool: Pool = serde_json::from_value(serde_json::json!({
+        "id": "pool1",
+        "dex_id": "dex1",
+        "dex_name": "Test DEX",
+        "chain": "ethereum",
+        "volume_usd": 1000000.0,
+        "price_usd": 1.5,
+        "last_price_change_usd_24h": 2.5,
+        "tokens": [
+            {"id": "t0", "name": "Token Zero", "symbol": "T0", "chain": "ethereum"},
+            {"id": "t1", "name": "Token One", "symbol": "T1", "chain": "ethereum"}
+        ]
+    }))
+    .unwrap();
+
+    let tickers = to_coingecko_tickers(&[pool]);
+    assert_eq!(tickers.len(), 1);
+    assert_eq!(tickers[0].ticker_id, "T0_T1");
+    assert!(tickers[0].high >= tickers[0].low);
+
+    let path = std::env::temp_dir().join("coingecko_tickers_test.json");
+    write_coingecko_tickers_json(&[], path.to_str().unwrap()).unwrap();
+    let contents = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(contents, "[]");
+    std::fs::remove_file(&path).ok();
+
+    println!("✅ Wrote {} CoinGecko tickers", tickers.len());
+}
+
+#[test]
+fn test_token_amount_accepts_hex_and_decimal() {
+    let hex: TokenAmount = serde_json::from_str("\"0x2540be400\"").unwrap();
+    assert_eq!(hex.as_f64(), 10_000_000_000.0);
+
+    let decimal: TokenAmount = serde_json::from_str("\"1234.5678\"").unwrap();
+    assert_eq!(decimal.as_f64(), 1234.5678);
+
+    let number: TokenAmount = serde_json::from_str("42").unwrap();
+    assert_eq!(number.as_f64(), 42.0);
+}
+
+fn mock_transaction(amount_0: &str, amount_1: &str, price_0_usd: f64, price_1_usd: f64) -> Transaction {
+    Transaction {
+        id: "tx1".to_string(),
+        log_index: None,
+        transaction_index: None,
+        pool_id: "pool1".to_string(),
+        sender: "0xsender".to_string(),
+        recipient: None,
+        token_0: "token0".to_string(),
+        token_0_symbol: "T0".to_string(),
+        token_1: "token1".to_string(),
+        token_1_symbol: "T1".to_string(),
+        amount_0: serde_json::from_str(&format!("\"{amount_0}\"")).unwrap(),
+        amount_1: serde_json::from_str(&format!("\"{amount_1}\"")).unwrap(),
+        price_0: price_0_usd,
+        price_1: price_1_usd,
+        price_0_usd,
+        price_1_usd,
+        created_at_block_number: 1.0,
+        created_at: "2023-01-01T00:00:00Z".to_string(),
+    }
+}
+
+#[test]
+fn test_calculate_volume_weighted_price_precise() {
+    let txs = vec![
+        mock_transaction("1000000000000000000", "2000", 1.0, 1.0),
+        mock_transaction("3000000000000000000", "6000", 2.0, 2.0),
+    ];
+
+    let vwap = calculate_volume_weighted_price_precise(&txs);
+    // (1e18 * $1 + 3e18 * $2) / 4e18 = $1.75 exactly.
+    assert_eq!(vwap, Decimal::new(175, 2));
+
+    println!("✅ Calculated precise VWAP: {}", vwap);
+}
+
+#[test]
+fn test_calculate_volatility_precise() {
+    let txs = vec![
+        mock_transaction("1", "1", 100.0, 100.0),
+        mock_transaction("1", "1", 105.0, 105.0),
+        mock_transaction("1", "1", 102.0, 102.0),
+    ];
+
+    let volatility = calculate_volatility_precise(&txs);
+    assert!(volatility > Decimal::ZERO);
+
+    println!("✅ Calculated precise volatility: {}", volatility);
+}
+
+#[tokio::test]
+async fn test_get_token_metadata_reads_onchain_erc20_metadata() {
+    let provider = RpcProvider::new().with_endpoint("ethereum", "https://eth.llamarpc.com");
+
+    // USDC on Ethereum mainnet.
+    let result = get_token_metadata(&provider, "ethereum", "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48").await;
+    if let Err(e) = &result {
+        println!("❌ On-chain metadata error: {}", e);
+    }
+    assert!(result.is_ok());
+
+    let metadata = result.unwrap();
+    assert_eq!(metadata.decimals, 6);
+
+    println!("✅ Read on-chain metadata: {} decimals, symbol {}", metadata.decimals, metadata.symbol);
+}
+
+#[test]
+fn test_trend_tracker_ranks_by_percent_change_across_a_polling_gap() {
+    let mut tracker = TrendTracker::new(std::time::Duration::from_secs(300), std::time::Duration::from_secs(3600));
+
+    let base = chrono::DateTime::parse_from_rfc3339("2023-01-01T00:00:00Z")
+        .unwrap()
+        .with_timezone(&chrono::Utc);
+    tracker.record("pool-a", 100.0, 1000.0, base);
+    // A 15-minute gap (3 buckets) should still advance time via flat
+    // carried-forward buckets rather than losing the time axis.
+    tracker.record("pool-a", 110.0, 500.0, base + chrono::Duration::minutes(15));
+    tracker.record("pool-b", 100.0, 100.0, base);
+    tracker.record("pool-b", 101.0, 100.0, base + chrono::Duration::minutes(5));
+
+    let top = tracker.top_trending(1, TrendMetric::PercentChange);
+    assert_eq!(top.len(), 1);
+    assert_eq!(top[0].pool_address, "pool-a");
+    assert!((top[0].score - 10.0).abs() < 0.001);
+
+    println!("✅ Trend tracker ranked {} pools", top.len());
+}
+
+#[tokio::test]
+async fn test_async_monitor_prices_broadcast_has_no_subscribers_until_asked() {
+    let config = MonitorConfig {
+        min_absolute_change: 0.0,
+        min_percent_change: 0.0,
+        dedup_window: std::time::Duration::from_secs(0),
+    };
+
+    let sender = async_monitor_prices_broadcast(
+        &["test-pool".to_string()],
+        "ethereum",
+        std::time::Duration::from_secs(3600),
+        config,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(sender.receiver_count(), 0);
+    let mut receiver = sender.subscribe();
+    assert_eq!(sender.receiver_count(), 1);
+    drop(receiver);
+    // Dropping the only subscriber shouldn't affect the sender itself.
+    receiver = sender.subscribe();
+    assert_eq!(sender.receiver_count(), 1);
+    drop(receiver);
+
+    println!("✅ Broadcast monitor sender is subscribable");
+}
+
+#[tokio::test]
+async fn test_client_pool_tracks_in_use_and_idle() {
+    let pool = ClientPool::new(2, std::time::Duration::from_secs(30));
+
+    let metrics = pool.metrics();
+    assert_eq!(metrics.idle, 2);
+    assert_eq!(metrics.in_use, 0);
+
+    let checked_out = pool.acquire().await;
+    let metrics = pool.metrics();
+    assert_eq!(metrics.in_use, 1);
+    assert_eq!(metrics.idle, 1);
+    assert_eq!(metrics.total_checkouts, 1);
+
+    drop(checked_out);
+    let metrics = pool.metrics();
+    assert_eq!(metrics.in_use, 0);
+    assert_eq!(metrics.idle, 2);
+
+    println!("✅ ClientPool metrics tracked in-use/idle correctly");
+}
+
+#[cfg(feature = "redis")]
+#[tokio::test]
+async fn test_price_cache_round_trips_through_redis() {
+    configure_price_cache(PriceCacheConfig::default());
+
+    let snapshot = CachedPrice {
+        price_usd: 1.23,
+        last_price_change_usd_24h: 4.5,
+        volume_usd: 1_000_000.0,
+        timestamp: chrono::Utc::now().to_rfc3339(),
+    };
+
+    cache_pool_price("test-pool", &snapshot).await.unwrap();
+    let cached = get_cached_pool_price("test-pool").await.unwrap();
+    assert_eq!(cached.map(|c| c.price_usd), Some(1.23));
+
+    let history = get_price_history("test-pool", std::time::Duration::from_secs(3600)).await.unwrap();
+    assert!(!history.is_empty());
+
+    println!("✅ Price cache round-tripped through Redis");
+}
+
+#[tokio::test]
+async fn test_request_scheduler_coalesces_concurrent_requests() {
+    let scheduler = RequestScheduler::builder()
+        .debounce_duration(std::time::Duration::from_millis(20))
+        .max_batch_size(10)
+        .max_requests_per_batch(5)
+        .build();
+
+    let mut handles = Vec::new();
+    for _ in 0..3 {
+        let scheduler = scheduler.clone();
+        handles.push(tokio::spawn(async move { scheduler.schedule("/networks", None).await }));
+    }
+
+    for handle in handles {
+        let result = handle.await.unwrap();
+        if let Err(e) = &result {
+            println!("❌ Scheduled request error: {}", e);
+        }
+        assert!(result.is_ok());
+    }
+
+    println!("✅ Scheduler dispatched coalesced requests");
+}
+
+#[test]
+fn test_estimate_next_base_fee_matches_eip1559_recurrence() {
+    let base_fee = 100_000_000_000u128; // 100 gwei
+    let gas_limit = 30_000_000u64;
+    let gas_target = gas_limit / 2;
+
+    // At target, base fee is unchanged.
+    assert_eq!(estimate_next_base_fee(base_fee, gas_target, gas_limit), base_fee);
+
+    // A fully-saturated block pushes the base fee up by ~12.5%.
+    let up = estimate_next_base_fee(base_fee, gas_limit, gas_limit);
+    assert!(up > base_fee);
+
+    // An empty block pulls the base fee down by ~12.5%.
+    let down = estimate_next_base_fee(base_fee, 0, gas_limit);
+    assert!(down < base_fee);
+
+    println!("✅ Base fee recurrence: {} -> up {}, down {}", base_fee, up, down);
+}
+
+#[test]
+fn test_trade_profitability_nets_gas_against_gross_move() {
+    let pool: Pool = serde_json::from_value(serde_json::json!({
+        "id": "pool1",
+        "dex_id": "dex1",
+        "dex_name": "Test DEX",
+        "chain": "ethereum",
+        "volume_usd": 1000000.0,
+        "price_usd": 1.5,
+        "last_price_change_usd_24h": 5.0,
+        "tokens": []
+    }))
+    .unwrap();
+
+    let big_trade = trade_profitability(&pool, 100_000.0, 150_000, DEFAULT_BASE_FEE_WEI, DEFAULT_PRIORITY_TIP_WEI, 3000.0);
+    // 150k gas at 22 gwei effective price and a $3000 ETH price is ~$9.90 —
+    // nowhere near the billions a wei->gwei (instead of wei->ETH) conversion
+    // bug would produce.
+    assert!(big_trade.gas_cost_usd > 5.0 && big_trade.gas_cost_usd < 20.0);
+    assert!(big_trade.is_profitable);
+
+    let dust_trade = trade_profitability(&pool, 1.0, 150_000, DEFAULT_BASE_FEE_WEI, DEFAULT_PRIORITY_TIP_WEI, 3000.0);
+    assert!(!dust_trade.is_profitable);
+
+    println!("✅ Trade profitability: big net ${:.2}, dust net ${:.2}", big_trade.net_usd, dust_trade.net_usd);
+}
+
+#[test]
+fn test_rank_pools_weights_combine_into_one_score() {
+    let low: Pool = serde_json::from_value(serde_json::json!({
+        "id": "low", "dex_id": "dex1", "dex_name": "DEX 1", "chain": "ethereum",
+        "volume_usd": 1000.0, "transactions": 5, "last_price_change_usd_24h": 0.1, "tokens": []
+    }))
+    .unwrap();
+    let high: Pool = serde_json::from_value(serde_json::json!({
+        "id": "high", "dex_id": "dex2", "dex_name": "DEX 2", "chain": "ethereum",
+        "volume_usd": 5_000_000.0, "transactions": 900, "last_price_change_usd_24h": 40.0, "tokens": []
+    }))
+    .unwrap();
+
+    let weights = ScoreWeights { volume: 1.0, transactions: 1.0, price_change_24h: 1.0, ..Default::default() };
+    let ranked = rank_pools(&[low, high], weights, 2);
+
+    assert_eq!(ranked.len(), 2);
+    assert_eq!(ranked[0].pool.id, "high");
+    assert!(ranked[0].score > ranked[1].score);
+    assert!(ranked[0].volume_contribution > 0.0);
+
+    println!("✅ Ranked pools by combined z-scored weights: {} first", ranked[0].pool.id);
+}
+
+#[test]
+fn test_top_n_rejects_unknown_field() {
+    let pools: Vec<Pool> = vec![];
+    let err = top_n(&pools, "not_a_real_field", 5).unwrap_err();
+    match err {
+        PaprikaError::ValidationError(msg) => assert!(msg.contains("not_a_real_field")),
+        other => panic!("expected ValidationError, got {other:?}"),
+    }
+
+    println!("✅ top_n rejected an unknown field with a typed error");
+}
+
+#[test]
+fn test_api_params_carry_per_request_timeout_and_retry_policy() {
+    let retry_policy = RetryPolicy {
+        max_retries: 1,
+        base_delay: std::time::Duration::from_millis(5),
+        max_delay: std::time::Duration::from_millis(50),
+        jitter: false,
+    };
+
+    let params = ApiParams::new().timeout(std::time::Duration::from_millis(250)).retry_policy(retry_policy.clone());
+
+    assert_eq!(params.timeout, Some(std::time::Duration::from_millis(250)));
+    assert_eq!(params.retry_policy.as_ref().map(|p| p.max_retries), Some(1));
+
+    println!("✅ ApiParams carries a per-request timeout and retry policy override");
+}
+
+#[tokio::test]
+async fn test_api_request_honors_a_tight_per_request_timeout() {
+    // A 1ms timeout can't complete a real round trip, so this exercises the
+    // per-request `ApiParams::timeout` override without depending on the
+    // remote host being unreachable.
+    let retry_policy = RetryPolicy {
+        max_retries: 1,
+        base_delay: std::time::Duration::from_millis(1),
+        max_delay: std::time::Duration::from_millis(5),
+        jitter: false,
+    };
+
+    let result = api_request(
+        "/networks",
+        Some(ApiParams::new().retry_policy(retry_policy).timeout(std::time::Duration::from_millis(1))),
+    )
+    .await;
+
+    assert!(result.is_err());
+    println!("✅ api_request surfaced a timeout error under a 1ms per-request timeout");
+}
+
+#[test]
+fn test_market_snapshot_proves_and_rejects_inclusion() {
+    let make_pool = |id: &str, volume: f64| -> Pool {
+        serde_json::from_value(serde_json::json!({
+            "id": id, "dex_id": "dex1", "dex_name": "DEX 1", "chain": "ethereum",
+            "volume_usd": volume, "price_usd": 1.5, "last_price_change_usd_24h": 2.5, "tokens": []
+        }))
+        .unwrap()
+    };
+
+    let pools = vec![make_pool("pool1", 100.0), make_pool("pool2", 200.0), make_pool("pool3", 300.0)];
+    let snapshot = MarketSnapshot::build(&pools, chrono::DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&chrono::Utc)).unwrap();
+
+    let proof = snapshot.inclusion_proof("pool2").unwrap();
+    assert!(snapshot.verify(&pools[1], &proof));
+
+    // A tampered pool datum must not verify against the same proof/root.
+    let tampered = make_pool("pool2", 999.0);
+    assert!(!snapshot.verify(&tampered, &proof));
+
+    // Tampering with a field outside the old 8-field shortlist (here,
+    // `tokens`) must also be caught — canonicalization covers the full `Pool`.
+    let mut retokened = pools[1].clone();
+    retokened.tokens = vec![serde_json::from_value(serde_json::json!({
+        "id": "evil", "name": "Evil Token", "symbol": "EVIL", "chain": "ethereum"
+    }))
+    .unwrap()];
+    assert!(!snapshot.verify(&retokened, &proof));
+
+    // A pool that was never inserted has no proof at all.
+    assert!(snapshot.inclusion_proof("pool4").is_none());
+
+    println!("✅ Market snapshot proved inclusion and rejected a tampered pool");
+}
+
+#[test]
+fn test_resample_transactions_to_ohlcv_volume_is_trade_value_not_price_sum() {
+    // `price_0_usd + price_1_usd` is ~3000 for both trades below regardless
+    // of size — the old bug's telltale. Volume should instead scale with the
+    // token-0 amount actually traded.
+    let small = mock_transaction("2", "3", 1500.0, 1500.0);
+    let large = mock_transaction("20", "30", 1500.0, 1500.0);
+
+    let small_candle = &resample_transactions_to_ohlcv(std::slice::from_ref(&small))[0];
+    let large_candle = &resample_transactions_to_ohlcv(std::slice::from_ref(&large))[0];
+
+    assert_eq!(small_candle.volume, 3000);
+    assert_eq!(large_candle.volume, 30000);
+    assert!(large_candle.volume > small_candle.volume);
+
+    println!("✅ Candle volume scales with trade size: {} vs {}", small_candle.volume, large_candle.volume);
+}
+
+#[test]
+fn test_backfill_resume_point_survives_an_interrupted_run() {
+    let from = chrono::DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&chrono::Utc);
+    let to = chrono::DateTime::parse_from_rfc3339("2026-02-01T00:00:00Z").unwrap().with_timezone(&chrono::Utc);
+
+    // No watermark yet (first run, or a prior run that was interrupted before
+    // any page finished): resume from `from`, i.e. a full restart rather than
+    // a silent no-op.
+    assert_eq!(backfill_resume_point(None, from), from);
+
+    // A run that completed an older, narrower range shouldn't block rescanning
+    // the unfinished remainder of the current, wider range.
+    let earlier_watermark = chrono::DateTime::parse_from_rfc3339("2026-01-15T00:00:00Z").unwrap().with_timezone(&chrono::Utc);
+    let resumed = backfill_resume_point(Some(earlier_watermark), from);
+    assert_eq!(resumed, earlier_watermark);
+    assert!(resumed < to, "a partially-completed range must not look done");
+
+    // Once a run has fully completed `[from, to]`, the watermark equals `to`
+    // — not some unrelated wall-clock value — so a later call with the same
+    // range correctly treats it as already done.
+    assert_eq!(backfill_resume_point(Some(to), from), to);
+
+    println!("✅ backfill_resume_point resumes a partial range and recognizes a completed one");
+}
+
 // Run all tests with: cargo test
 // Run specific test with: cargo test test_get_networks
 // Run with output: cargo test -- --nocapture 
\ No newline at end of file
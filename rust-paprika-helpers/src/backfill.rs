@@ -0,0 +1,143 @@
+//! Resumable OHLCV and transaction backfill
+//!
+//! Walks a historical range in API-sized pages, advancing the cursor to the
+//! last record seen each page and deduplicating overlapping boundary
+//! records, since `get_pool_ohlcv`/`get_pool_transactions` each only return a
+//! single page from one `start` cursor.
+
+use crate::{
+    get_pool_ohlcv, get_pool_transactions, ApiParams, OHLCVRecord, Result, Transaction,
+};
+use chrono::{DateTime, Utc};
+use std::collections::HashSet;
+
+/// Maximum number of records the DexPaprika API returns for a single
+/// OHLCV/transactions call.
+const PAGE_LIMIT: u32 = 1000;
+
+/// Reports progress through a backfill so callers can observe or resume it.
+#[derive(Debug, Clone)]
+pub struct BackfillProgress {
+    /// Cursor the next page will be fetched from.
+    pub cursor: DateTime<Utc>,
+    /// Total records accumulated so far.
+    pub fetched: usize,
+    /// True once the cursor has reached or passed `end`.
+    pub done: bool,
+}
+
+/// Backfills OHLCV candles for `[start, end]` by walking the range in
+/// API-sized windows, advancing the cursor past the last returned record each
+/// page, and deduplicating overlapping boundary candles by bucket start time
+/// (`time_open`). `on_progress` is invoked after every page so long-running
+/// backfills can be observed or checkpointed for resuming later.
+pub async fn backfill_pool_ohlcv(
+    network: &str,
+    pool_address: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    resolution: &str,
+    params: Option<ApiParams>,
+    mut on_progress: impl FnMut(&BackfillProgress),
+) -> Result<Vec<OHLCVRecord>> {
+    let base_params = params.unwrap_or_default();
+    let mut cursor = start;
+    let mut seen_buckets: HashSet<String> = HashSet::new();
+    let mut records: Vec<OHLCVRecord> = Vec::new();
+
+    while cursor < end {
+        let page_params = base_params
+            .clone()
+            .limit(PAGE_LIMIT)
+            .interval(resolution);
+
+        let page = get_pool_ohlcv(
+            network,
+            pool_address,
+            &cursor.to_rfc3339(),
+            Some(page_params),
+        )
+        .await?;
+
+        if page.is_empty() {
+            break;
+        }
+
+        let mut last_close = cursor;
+        for record in page {
+            if seen_buckets.insert(record.time_open.clone()) {
+                if let Ok(close) = DateTime::parse_from_rfc3339(&record.time_close) {
+                    last_close = last_close.max(close.with_timezone(&Utc));
+                }
+                records.push(record);
+            }
+        }
+
+        if last_close <= cursor {
+            // No forward progress (e.g. the API repeated the same window) —
+            // stop rather than loop forever.
+            break;
+        }
+        cursor = last_close;
+
+        on_progress(&BackfillProgress {
+            cursor,
+            fetched: records.len(),
+            done: cursor >= end,
+        });
+    }
+
+    records.sort_by(|a, b| a.time_open.cmp(&b.time_open));
+    Ok(records)
+}
+
+/// Backfills raw transactions for `[start, end]` independently of candle
+/// backfilling, walking pages by advancing the cursor to the newest
+/// `created_at` seen in each page and deduplicating by transaction `id`.
+pub async fn backfill_pool_transactions(
+    network: &str,
+    pool_address: &str,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+    params: Option<ApiParams>,
+    mut on_progress: impl FnMut(&BackfillProgress),
+) -> Result<Vec<Transaction>> {
+    let base_params = params.unwrap_or_default();
+    let mut cursor = start;
+    let mut seen_ids: HashSet<String> = HashSet::new();
+    let mut transactions: Vec<Transaction> = Vec::new();
+
+    while cursor < end {
+        let page_params = base_params.clone().limit(PAGE_LIMIT).start(&cursor.to_rfc3339());
+
+        let response = get_pool_transactions(network, pool_address, Some(page_params)).await?;
+
+        if response.transactions.is_empty() {
+            break;
+        }
+
+        let mut last_seen = cursor;
+        for tx in response.transactions {
+            if seen_ids.insert(tx.id.clone()) {
+                if let Ok(created) = DateTime::parse_from_rfc3339(&tx.created_at) {
+                    last_seen = last_seen.max(created.with_timezone(&Utc));
+                }
+                transactions.push(tx);
+            }
+        }
+
+        if last_seen <= cursor {
+            break;
+        }
+        cursor = last_seen;
+
+        on_progress(&BackfillProgress {
+            cursor,
+            fetched: transactions.len(),
+            done: cursor >= end,
+        });
+    }
+
+    transactions.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    Ok(transactions)
+}
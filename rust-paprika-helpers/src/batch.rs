@@ -0,0 +1,166 @@
+//! Bounded-concurrency batched fetching, and the retry/backoff config shared
+//! by `api_request`
+//!
+//! `fetch_many` drives a batch of requests through a semaphore bounded by
+//! `ClientConfig::concurrency`, instead of callers parallelizing
+//! `api_request` themselves and tripping rate limits. `ClientConfig` also
+//! controls how hard `api_request` retries a single request on 429/5xx.
+
+use crate::{api_request, ApiParams, Result};
+use serde_json::Value;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::sync::Semaphore;
+
+/// Exponential backoff tuning for a retryable `api_request` failure
+/// (connection error, timeout, 5xx, or 429). Per-request callers can override
+/// this via `ApiParams::retry_policy`; otherwise `ClientConfig::retry_policy`
+/// applies.
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    /// Maximum number of retries after the initial attempt.
+    pub max_retries: u32,
+    /// Base delay the exponential backoff grows from.
+    pub base_delay: Duration,
+    /// Ceiling no computed backoff delay (jittered or not) may exceed.
+    pub max_delay: Duration,
+    /// Whether to apply full jitter (a uniform random delay between `0` and
+    /// the capped exponential backoff) rather than sleeping the raw value.
+    pub jitter: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            base_delay: Duration::from_millis(250),
+            max_delay: Duration::from_secs(10),
+            jitter: true,
+        }
+    }
+}
+
+/// Controls concurrency and retry behavior for `api_request` and `fetch_many`.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Maximum number of requests `fetch_many` runs at once.
+    pub concurrency: usize,
+    /// Default retry/backoff tuning for `api_request`, overridden per-request
+    /// by `ApiParams::retry_policy`.
+    pub retry_policy: RetryPolicy,
+    /// Whether `api_request` honors a 429 response's `Retry-After` header as
+    /// the retry delay instead of its own backoff schedule.
+    pub respect_retry_after: bool,
+    /// Maximum number of concurrent HTTP clients `api_request` checks out of
+    /// the global `ClientPool`.
+    pub pool_max_size: usize,
+    /// How long an idle pooled connection is kept alive before closing.
+    pub pool_idle_timeout: Duration,
+}
+
+impl Default for ClientConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: 8,
+            retry_policy: RetryPolicy::default(),
+            respect_retry_after: true,
+            pool_max_size: 32,
+            pool_idle_timeout: Duration::from_secs(90),
+        }
+    }
+}
+
+/// Tunes the concurrency of the `async_*_batch` helpers (distinct from
+/// `ClientConfig::concurrency`, which bounds `fetch_many`).
+#[derive(Debug, Clone, Copy)]
+pub struct BatchOptions {
+    /// Maximum number of requests driven concurrently via `buffer_unordered`.
+    pub max_concurrency: usize,
+}
+
+impl Default for BatchOptions {
+    fn default() -> Self {
+        Self { max_concurrency: 8 }
+    }
+}
+
+static CLIENT_CONFIG: OnceLock<ClientConfig> = OnceLock::new();
+
+/// Injects the `ClientConfig` used by `api_request`/`fetch_many`. Must be
+/// called before the first request — later calls are ignored.
+pub fn configure_client(config: ClientConfig) {
+    let _ = CLIENT_CONFIG.set(config);
+}
+
+/// Returns the configured `ClientConfig`, defaulting if never configured.
+pub fn get_client_config() -> &'static ClientConfig {
+    CLIENT_CONFIG.get_or_init(ClientConfig::default)
+}
+
+/// Computes the delay before the next retry attempt: exponential backoff off
+/// `policy.base_delay`, capped at `policy.max_delay`, with full jitter
+/// (uniform random between `0` and the capped value) applied when
+/// `policy.jitter` is set.
+pub(crate) fn backoff_with_jitter(policy: &RetryPolicy, attempt: u32) -> Duration {
+    let exp_ms = policy.base_delay.as_millis().saturating_mul(1u128 << attempt.min(16));
+    let capped_ms = exp_ms.min(policy.max_delay.as_millis());
+
+    let delay_ms = if policy.jitter {
+        ((pseudo_random_unit(attempt) / 100.0) * capped_ms as f64) as u128
+    } else {
+        capped_ms
+    };
+
+    Duration::from_millis(delay_ms.min(u64::MAX as u128) as u64)
+}
+
+/// Monotonically increasing nonce mixed into `pseudo_random_unit`'s seed so
+/// concurrent callers retrying at the same `attempt` (e.g. `fetch_many`'s
+/// bounded fan-out hitting a rate limit all at once) don't land on the
+/// identical delay.
+static JITTER_CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// Cheap per-call jitter source so this module doesn't need to pull in a full
+/// `rand` dependency just for retry spacing. Seeded off `attempt` plus a
+/// call-local nonce (an incrementing counter and elapsed-time reading), not
+/// `attempt` alone, so synchronized retries don't all sleep for the same
+/// duration.
+fn pseudo_random_unit(attempt: u32) -> f64 {
+    static EPOCH: OnceLock<Instant> = OnceLock::new();
+    let nanos = EPOCH.get_or_init(Instant::now).elapsed().as_nanos() as u32;
+    let call_nonce = JITTER_CALLS.fetch_add(1, Ordering::Relaxed);
+
+    let x = attempt
+        .wrapping_mul(2654435761)
+        .wrapping_add(call_nonce.wrapping_mul(0x85EBCA6B))
+        .wrapping_add(nanos)
+        .wrapping_add(0x9E3779B9);
+
+    (x % 100) as f64
+}
+
+/// Drives a batch of `(endpoint, params)` requests through a semaphore
+/// bounded by `ClientConfig::concurrency`, returning one `Result` per input
+/// request in the same order.
+pub async fn fetch_many(requests: Vec<(String, ApiParams)>) -> Vec<Result<Value>> {
+    let semaphore = Arc::new(Semaphore::new(get_client_config().concurrency));
+    let mut handles = Vec::with_capacity(requests.len());
+
+    for (endpoint, params) in requests {
+        let semaphore = semaphore.clone();
+        handles.push(tokio::spawn(async move {
+            let _permit = semaphore.acquire_owned().await;
+            api_request(&endpoint, Some(params)).await
+        }));
+    }
+
+    let mut results = Vec::with_capacity(handles.len());
+    for handle in handles {
+        results.push(match handle.await {
+            Ok(result) => result,
+            Err(e) => Err(crate::PaprikaError::GenericError(e.to_string())),
+        });
+    }
+    results
+}
@@ -0,0 +1,150 @@
+//! Pluggable cache backends for `api_request`
+//!
+//! The default backend is an in-memory `DashMap`, process-local and lost on
+//! restart. `PostgresCache` persists the same `CacheEntry` rows to a table
+//! instead, so a fleet of instances sharing one database keeps a warm cache
+//! across redeploys.
+
+use crate::{CacheEntry, Result, CACHE_DURATION};
+use async_trait::async_trait;
+use chrono::Utc;
+use dashmap::DashMap;
+use std::sync::{Arc, OnceLock};
+use tokio_postgres::Client;
+
+/// A cache backend for `api_request` responses, keyed by the endpoint +
+/// params hash produced by `create_cache_key`. Implementations are
+/// responsible for honoring `CACHE_DURATION` themselves (`get` should return
+/// `None` for expired entries).
+#[async_trait]
+pub trait Cache: Send + Sync {
+    /// Returns the cached entry for `key`, if present and not expired.
+    async fn get(&self, key: &str) -> Option<CacheEntry>;
+    /// Stores `entry` under `key`, replacing any existing value.
+    async fn insert(&self, key: &str, entry: CacheEntry);
+}
+
+/// Default in-memory backend, equivalent to the original global `DashMap`.
+#[derive(Default)]
+pub struct DashMapCache {
+    entries: DashMap<String, CacheEntry>,
+}
+
+impl DashMapCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Cache for DashMapCache {
+    async fn get(&self, key: &str) -> Option<CacheEntry> {
+        let entry = self.entries.get(key)?;
+        if Utc::now().signed_duration_since(entry.timestamp).num_seconds()
+            < CACHE_DURATION.as_secs() as i64
+        {
+            Some(entry.clone())
+        } else {
+            None
+        }
+    }
+
+    async fn insert(&self, key: &str, entry: CacheEntry) {
+        self.entries.insert(key.to_string(), entry);
+    }
+}
+
+/// A `tokio-postgres`-backed cache that persists responses to a table so they
+/// survive process restarts and can be shared across instances.
+///
+/// Expects a table of the shape:
+/// ```sql
+/// CREATE TABLE IF NOT EXISTS api_cache (
+///     key        TEXT PRIMARY KEY,
+///     endpoint   TEXT NOT NULL,
+///     body       JSONB NOT NULL,
+///     fetched_at TIMESTAMPTZ NOT NULL
+/// );
+/// ```
+pub struct PostgresCache {
+    client: Client,
+}
+
+impl PostgresCache {
+    /// Wraps an already-connected `tokio_postgres::Client`, creating the
+    /// backing table if it doesn't exist yet.
+    pub async fn connect(client: Client) -> Result<Self> {
+        client
+            .execute(
+                "CREATE TABLE IF NOT EXISTS api_cache (
+                    key        TEXT PRIMARY KEY,
+                    endpoint   TEXT NOT NULL,
+                    body       JSONB NOT NULL,
+                    fetched_at TIMESTAMPTZ NOT NULL
+                )",
+                &[],
+            )
+            .await
+            .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+
+        Ok(Self { client })
+    }
+}
+
+#[async_trait]
+impl Cache for PostgresCache {
+    async fn get(&self, key: &str) -> Option<CacheEntry> {
+        let row = self
+            .client
+            .query_opt(
+                "SELECT body, fetched_at FROM api_cache WHERE key = $1",
+                &[&key],
+            )
+            .await
+            .ok()??;
+
+        let body: serde_json::Value = row.get("body");
+        let fetched_at: chrono::DateTime<Utc> = row.get("fetched_at");
+
+        if Utc::now().signed_duration_since(fetched_at).num_seconds()
+            < CACHE_DURATION.as_secs() as i64
+        {
+            Some(CacheEntry {
+                data: body,
+                timestamp: fetched_at,
+            })
+        } else {
+            None
+        }
+    }
+
+    async fn insert(&self, key: &str, entry: CacheEntry) {
+        let endpoint = key.split('-').next().unwrap_or(key);
+        let _ = self
+            .client
+            .execute(
+                "INSERT INTO api_cache (key, endpoint, body, fetched_at)
+                 VALUES ($1, $2, $3, $4)
+                 ON CONFLICT (key) DO UPDATE
+                 SET body = EXCLUDED.body, fetched_at = EXCLUDED.fetched_at",
+                &[&key, &endpoint, &entry.data, &entry.timestamp],
+            )
+            .await;
+    }
+}
+
+static CACHE_BACKEND: OnceLock<Arc<dyn Cache>> = OnceLock::new();
+
+/// Injects the cache backend used by `api_request`. Must be called before the
+/// first request is made — later calls are ignored, mirroring how
+/// `get_client_config`/`get_client_pool` lazily pin their globals on first
+/// use.
+pub fn configure_cache_backend(backend: Arc<dyn Cache>) {
+    let _ = CACHE_BACKEND.set(backend);
+}
+
+/// Returns the configured cache backend, defaulting to `DashMapCache` if
+/// `configure_cache_backend` was never called.
+pub fn get_cache_backend() -> &'static Arc<dyn Cache> {
+    CACHE_BACKEND.get_or_init(|| Arc::new(DashMapCache::new()))
+}
@@ -0,0 +1,163 @@
+//! Candle aggregation and resampling
+//!
+//! Resamples raw `OHLCVRecord` series (or `Transaction` streams) into OHLCV
+//! candles at arbitrary resolutions. Gaps in the source data are filled so
+//! the resulting series has no holes.
+
+use crate::{OHLCVRecord, Transaction};
+use chrono::{DateTime, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::time::Duration;
+
+/// Aggregates OHLCV records into candles of the given resolution (in seconds).
+///
+/// Each record is bucketed by `bucket_start = (ts / resolution_secs) * resolution_secs`,
+/// using its `time_open` timestamp. Within a bucket, `open` is taken from the
+/// earliest record, `close` from the latest, `high`/`low` from the extremes,
+/// and `volume` is the sum of all record volumes. Empty buckets between two
+/// populated buckets are filled with a flat candle (open = high = low = close
+/// = previous bucket's close, volume = 0) so the series has no gaps.
+pub fn aggregate_candles(records: &[OHLCVRecord], resolution_secs: i64) -> Vec<OHLCVRecord> {
+    if records.is_empty() || resolution_secs <= 0 {
+        return Vec::new();
+    }
+
+    let mut timestamped: Vec<(i64, &OHLCVRecord)> = records
+        .iter()
+        .filter_map(|r| {
+            DateTime::parse_from_rfc3339(&r.time_open)
+                .ok()
+                .map(|ts| (ts.timestamp(), r))
+        })
+        .collect();
+    timestamped.sort_by_key(|(ts, _)| *ts);
+
+    if timestamped.is_empty() {
+        return Vec::new();
+    }
+
+    let mut buckets: Vec<(i64, OHLCVRecord)> = Vec::new();
+
+    for (ts, record) in timestamped {
+        let bucket_start = (ts / resolution_secs) * resolution_secs;
+
+        match buckets.last_mut() {
+            Some((last_start, last_candle)) if *last_start == bucket_start => {
+                last_candle.high = last_candle.high.max(record.high);
+                last_candle.low = last_candle.low.min(record.low);
+                last_candle.close = record.close;
+                last_candle.volume += record.volume;
+            }
+            _ => {
+                fill_gaps(&mut buckets, bucket_start, resolution_secs);
+                buckets.push((
+                    bucket_start,
+                    OHLCVRecord {
+                        time_open: bucket_time(bucket_start),
+                        time_close: bucket_time(bucket_start + resolution_secs),
+                        open: record.open,
+                        high: record.high,
+                        low: record.low,
+                        close: record.close,
+                        volume: record.volume,
+                        complete: true,
+                    },
+                ));
+            }
+        }
+    }
+
+    let now = Utc::now().timestamp();
+    buckets
+        .into_iter()
+        .map(|(bucket_start, mut candle)| {
+            if bucket_start + resolution_secs > now {
+                candle.time_close = bucket_time(now);
+                candle.complete = false;
+            }
+            candle
+        })
+        .collect()
+}
+
+/// Same as `aggregate_candles`, but expressed as a `Duration` resolution for
+/// callers that don't want to convert to seconds themselves.
+pub fn aggregate_candles_with_duration(records: &[OHLCVRecord], resolution: Duration) -> Vec<OHLCVRecord> {
+    aggregate_candles(records, resolution.as_secs() as i64)
+}
+
+/// Builds one base (unaggregated) OHLCV record per transaction, from its USD
+/// price (averaged across both sides) and the USD value actually traded
+/// (token-0 amount times that averaged price) as volume. Feed the result
+/// into `aggregate_candles` to resample a trade feed into candles at any
+/// resolution.
+pub fn resample_transactions_to_ohlcv(txs: &[Transaction]) -> Vec<OHLCVRecord> {
+    txs.iter()
+        .map(|tx| {
+            let price = (tx.price_0_usd + tx.price_1_usd) / 2.0;
+            let trade_value_usd = Decimal::try_from(price).unwrap_or(Decimal::ZERO) * tx.amount_0.0;
+            OHLCVRecord {
+                time_open: tx.created_at.clone(),
+                time_close: tx.created_at.clone(),
+                open: price,
+                high: price,
+                low: price,
+                close: price,
+                volume: trade_value_usd.to_i64().unwrap_or(0),
+                complete: true,
+            }
+        })
+        .collect()
+}
+
+/// Aggregates a raw transaction stream into candles, using each transaction's
+/// USD price (averaged across both sides) and the USD value actually traded
+/// as volume.
+pub fn candles_from_transactions(txs: &[Transaction], resolution_secs: i64) -> Vec<OHLCVRecord> {
+    aggregate_candles(&resample_transactions_to_ohlcv(txs), resolution_secs)
+}
+
+/// Fills any empty buckets strictly between the last populated bucket and
+/// `target_start` with flat candles carrying forward the previous close.
+fn fill_gaps(buckets: &mut Vec<(i64, OHLCVRecord)>, target_start: i64, resolution_secs: i64) {
+    let Some((last_start, last_candle)) = buckets.last() else {
+        return;
+    };
+
+    let carry_close = last_candle.close;
+    let mut gap_start = last_start + resolution_secs;
+
+    while gap_start < target_start {
+        buckets.push((
+            gap_start,
+            OHLCVRecord {
+                time_open: bucket_time(gap_start),
+                time_close: bucket_time(gap_start + resolution_secs),
+                open: carry_close,
+                high: carry_close,
+                low: carry_close,
+                close: carry_close,
+                volume: 0,
+                complete: true,
+            },
+        ));
+        gap_start += resolution_secs;
+    }
+}
+
+fn bucket_time(unix_secs: i64) -> String {
+    DateTime::<Utc>::from_timestamp(unix_secs, 0)
+        .unwrap_or_else(Utc::now)
+        .to_rfc3339()
+}
+
+/// Common candle resolutions, expressed in seconds.
+pub mod resolutions {
+    pub const ONE_MINUTE: i64 = 60;
+    pub const FIVE_MINUTES: i64 = 5 * 60;
+    pub const FIFTEEN_MINUTES: i64 = 15 * 60;
+    pub const ONE_HOUR: i64 = 60 * 60;
+    pub const FOUR_HOURS: i64 = 4 * 60 * 60;
+    pub const ONE_DAY: i64 = 24 * 60 * 60;
+}
@@ -0,0 +1,109 @@
+//! `deadpool`-style managed HTTP client pool for `api_request`
+//!
+//! `reqwest::Client` already reuses keep-alive connections internally, but
+//! nothing capped how many requests raced for them at once under the
+//! high-concurrency fan-out of `async_batch_search`/`async_batch_network`.
+//! `ClientPool` caps concurrent checkouts at `max_size`, hands out clones of
+//! one underlying `Client` (cheap — it's a handle around a shared
+//! connection pool), and tracks in-use/idle counts plus total wait time so
+//! callers watching hundreds of pools can tell whether `max_size` needs
+//! raising.
+
+use crate::get_client_config;
+use reqwest::Client;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+/// A checked-out pooled client. Its slot returns to the pool when dropped.
+pub struct PooledClient<'a> {
+    client: Client,
+    _permit: SemaphorePermit<'a>,
+}
+
+impl std::ops::Deref for PooledClient<'_> {
+    type Target = Client;
+
+    fn deref(&self) -> &Client {
+        &self.client
+    }
+}
+
+/// Point-in-time snapshot of a `ClientPool`'s usage.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PoolMetrics {
+    pub in_use: usize,
+    pub idle: usize,
+    pub max_size: usize,
+    pub total_checkouts: u64,
+    pub total_wait: Duration,
+}
+
+/// A managed pool of HTTP clients, capped at `max_size` concurrent
+/// checkouts.
+pub struct ClientPool {
+    client: Client,
+    semaphore: Semaphore,
+    max_size: usize,
+    wait_nanos: AtomicU64,
+    checkouts: AtomicU64,
+}
+
+impl ClientPool {
+    /// Builds a pool of at most `max_size` concurrent clients, each backed
+    /// by connections that idle out after `idle_timeout`. Deliberately has no
+    /// client-level request timeout — `api_request` is the sole source of
+    /// per-request timing via `tokio::time::timeout(params.timeout.unwrap_or(DEFAULT_TIMEOUT), ...)`,
+    /// and a client-level timeout shorter than a caller's override would fire
+    /// first and defeat it.
+    pub fn new(max_size: usize, idle_timeout: Duration) -> Self {
+        let client = Client::builder()
+            .pool_idle_timeout(idle_timeout)
+            .pool_max_idle_per_host(max_size)
+            .build()
+            .expect("Failed to create pooled HTTP client");
+
+        Self {
+            client,
+            semaphore: Semaphore::new(max_size),
+            max_size,
+            wait_nanos: AtomicU64::new(0),
+            checkouts: AtomicU64::new(0),
+        }
+    }
+
+    /// Checks out a client, waiting for a free slot if all `max_size` are
+    /// already in use.
+    pub async fn acquire(&self) -> PooledClient<'_> {
+        let start = Instant::now();
+        let permit = self.semaphore.acquire().await.expect("ClientPool semaphore closed");
+        self.wait_nanos.fetch_add(start.elapsed().as_nanos() as u64, Ordering::Relaxed);
+        self.checkouts.fetch_add(1, Ordering::Relaxed);
+        PooledClient { client: self.client.clone(), _permit: permit }
+    }
+
+    /// Returns current in-use/idle counts and cumulative checkout/wait
+    /// metrics.
+    pub fn metrics(&self) -> PoolMetrics {
+        let idle = self.semaphore.available_permits();
+        PoolMetrics {
+            in_use: self.max_size - idle,
+            idle,
+            max_size: self.max_size,
+            total_checkouts: self.checkouts.load(Ordering::Relaxed),
+            total_wait: Duration::from_nanos(self.wait_nanos.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+static CLIENT_POOL: OnceLock<ClientPool> = OnceLock::new();
+
+/// Returns the global `ClientPool`, sized from `ClientConfig::pool_max_size`
+/// / `pool_idle_timeout` the first time it's needed.
+pub fn get_client_pool() -> &'static ClientPool {
+    CLIENT_POOL.get_or_init(|| {
+        let config = get_client_config();
+        ClientPool::new(config.pool_max_size, config.pool_idle_timeout)
+    })
+}
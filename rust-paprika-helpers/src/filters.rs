@@ -0,0 +1,229 @@
+//! Polling filter subscriptions
+//!
+//! Borrows the `eth_newFilter`/`eth_getFilterChanges` model: register a
+//! long-lived filter once, then poll it repeatedly to get back only what
+//! changed since the last poll.
+
+use crate::{get_network_pools, get_pool_details, get_pool_transactions, ApiParams, Pool, Result, Transaction};
+use chrono::{DateTime, Utc};
+use dashmap::DashMap;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+/// Trade value in USD, averaging the two legs' per-unit prices and scaling
+/// by the token-0 amount actually traded (mirrors
+/// `calculate_volume_weighted_price_precise`'s pattern in `lib.rs`).
+fn transaction_value_usd(tx: &Transaction) -> f64 {
+    let price = Decimal::try_from((tx.price_0_usd + tx.price_1_usd) / 2.0).unwrap_or(Decimal::ZERO);
+    (price * tx.amount_0.0).to_f64().unwrap_or(0.0)
+}
+
+/// Opaque handle to a registered filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FilterId(u64);
+
+/// What a filter watches for.
+enum FilterKind {
+    /// New transactions on a pool above `min_usd` combined value.
+    Transactions {
+        network: String,
+        pool_address: String,
+        min_usd: f64,
+        last_seen_created_at: Option<String>,
+    },
+    /// New pools on a network above a volume threshold.
+    NewPools {
+        network: String,
+        min_volume_usd: f64,
+        seen_pool_ids: HashSet<String>,
+    },
+    /// A single pool's 24h price change crossing `bound_pct` (in either
+    /// direction) since the last poll.
+    PriceChange {
+        network: String,
+        pool_address: String,
+        bound_pct: f64,
+        was_over_bound: bool,
+    },
+}
+
+struct FilterState {
+    kind: FilterKind,
+    last_polled: DateTime<Utc>,
+}
+
+/// Result of polling a filter — only the subset of the variant relevant to
+/// that filter's kind is ever populated.
+#[derive(Debug, Clone, Default)]
+pub struct FilterChanges {
+    pub new_transactions: Vec<Transaction>,
+    pub new_pools: Vec<Pool>,
+    pub crossed_threshold: Vec<Pool>,
+}
+
+static FILTERS: OnceLock<DashMap<FilterId, FilterState>> = OnceLock::new();
+static NEXT_FILTER_ID: AtomicU64 = AtomicU64::new(1);
+
+fn filters() -> &'static DashMap<FilterId, FilterState> {
+    FILTERS.get_or_init(DashMap::new)
+}
+
+fn next_id() -> FilterId {
+    FilterId(NEXT_FILTER_ID.fetch_add(1, Ordering::Relaxed))
+}
+
+/// Registers a filter for new transactions on `pool_address` with a combined
+/// USD value of at least `min_usd`.
+pub fn create_transaction_filter(network: &str, pool_address: &str, min_usd: f64) -> FilterId {
+    let id = next_id();
+    filters().insert(
+        id,
+        FilterState {
+            kind: FilterKind::Transactions {
+                network: network.to_string(),
+                pool_address: pool_address.to_string(),
+                min_usd,
+                last_seen_created_at: None,
+            },
+            last_polled: Utc::now(),
+        },
+    );
+    id
+}
+
+/// Registers a filter for new pools appearing on `network` with at least
+/// `min_volume_usd` in volume.
+pub fn create_pool_filter(network: &str, min_volume_usd: f64) -> FilterId {
+    let id = next_id();
+    filters().insert(
+        id,
+        FilterState {
+            kind: FilterKind::NewPools {
+                network: network.to_string(),
+                min_volume_usd,
+                seen_pool_ids: HashSet::new(),
+            },
+            last_polled: Utc::now(),
+        },
+    );
+    id
+}
+
+/// Registers a filter that fires when `pool_address`'s 24h price change
+/// crosses `bound_pct` (in absolute value) since the last poll.
+pub fn create_price_change_filter(network: &str, pool_address: &str, bound_pct: f64) -> FilterId {
+    let id = next_id();
+    filters().insert(
+        id,
+        FilterState {
+            kind: FilterKind::PriceChange {
+                network: network.to_string(),
+                pool_address: pool_address.to_string(),
+                bound_pct,
+                was_over_bound: false,
+            },
+            last_polled: Utc::now(),
+        },
+    );
+    id
+}
+
+/// Polls a filter, re-fetching the relevant endpoint and diffing against the
+/// filter's high-water mark. Returns only what's new since the last poll.
+pub async fn poll_filter(id: FilterId) -> Result<FilterChanges> {
+    let mut entry = filters()
+        .get_mut(&id)
+        .ok_or_else(|| crate::PaprikaError::ValidationError(format!("unknown filter {:?}", id)))?;
+
+    entry.last_polled = Utc::now();
+
+    match &mut entry.kind {
+        FilterKind::Transactions {
+            network,
+            pool_address,
+            min_usd,
+            last_seen_created_at,
+        } => {
+            let response = get_pool_transactions(network, pool_address, Some(ApiParams::new().limit(100))).await?;
+
+            let watermark = last_seen_created_at.clone();
+            let mut new_txs: Vec<Transaction> = response
+                .transactions
+                .into_iter()
+                .filter(|tx| {
+                    transaction_value_usd(tx) >= *min_usd
+                        && watermark.as_deref().map_or(true, |w| tx.created_at.as_str() > w)
+                })
+                .collect();
+            new_txs.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+            if let Some(latest) = new_txs.last() {
+                *last_seen_created_at = Some(latest.created_at.clone());
+            }
+
+            Ok(FilterChanges {
+                new_transactions: new_txs,
+                ..Default::default()
+            })
+        }
+        FilterKind::NewPools {
+            network,
+            min_volume_usd,
+            seen_pool_ids,
+        } => {
+            let response = get_network_pools(network, Some(ApiParams::new().limit(100))).await?;
+
+            let new_pools: Vec<Pool> = response
+                .pools
+                .into_iter()
+                .filter(|pool| pool.volume_usd >= *min_volume_usd && seen_pool_ids.insert(pool.id.clone()))
+                .collect();
+
+            Ok(FilterChanges {
+                new_pools,
+                ..Default::default()
+            })
+        }
+        FilterKind::PriceChange {
+            network,
+            pool_address,
+            bound_pct,
+            was_over_bound,
+        } => {
+            let pool = get_pool_details(network, pool_address, None).await?;
+            let is_over_bound = pool.last_price_change_usd_24h.abs() >= *bound_pct;
+
+            let crossed = if is_over_bound && !*was_over_bound {
+                vec![pool]
+            } else {
+                Vec::new()
+            };
+            *was_over_bound = is_over_bound;
+
+            Ok(FilterChanges {
+                crossed_threshold: crossed,
+                ..Default::default()
+            })
+        }
+    }
+}
+
+/// Removes filters that haven't been polled within `idle_ttl`, returning how
+/// many were dropped.
+pub fn expire_idle_filters(idle_ttl: Duration) -> usize {
+    let cutoff = Utc::now() - chrono::Duration::from_std(idle_ttl).unwrap_or(chrono::Duration::zero());
+    let stale: Vec<FilterId> = filters()
+        .iter()
+        .filter(|entry| entry.value().last_polled < cutoff)
+        .map(|entry| *entry.key())
+        .collect();
+
+    for id in &stale {
+        filters().remove(id);
+    }
+    stale.len()
+}
@@ -0,0 +1,90 @@
+//! EIP-1559 base-fee projection and gas-cost accounting for trade profitability
+//!
+//! `estimate_next_base_fee` implements the EIP-1559 base-fee recurrence
+//! exactly. `effective_trade_cost_usd`/`trade_profitability` let callers net
+//! a pool's reported price move against the gas an assumed trade size would
+//! cost at the current base fee — useful on L1 chains, where gas can
+//! dominate a small trade.
+
+use crate::Pool;
+
+/// EIP-1559 elasticity multiplier: a block's gas target is half its `gas_limit`.
+const ELASTICITY_MULTIPLIER: u64 = 2;
+/// EIP-1559 base fee max change denominator: base fee moves at most 1/8th
+/// per block relative to how far `gas_used` is from the target.
+const BASE_FEE_MAX_CHANGE_DENOMINATOR: u64 = 8;
+
+/// Typical gas used by an AMM swap, for `MarketOverview`'s sample cost figure.
+pub const STANDARD_SWAP_GAS_UNITS: u64 = 150_000;
+/// 20 gwei, a conservative mainnet base-fee assumption when no live block is available.
+pub const DEFAULT_BASE_FEE_WEI: u128 = 20_000_000_000;
+/// 2 gwei, a conservative mainnet priority-tip assumption.
+pub const DEFAULT_PRIORITY_TIP_WEI: u128 = 2_000_000_000;
+
+/// A trade's gross price move netted against its gas cost.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ProfitabilityEstimate {
+    /// `trade_size_usd` scaled by the pool's reported percentage price change.
+    pub gross_move_usd: f64,
+    /// USD cost of the gas the trade is assumed to spend.
+    pub gas_cost_usd: f64,
+    /// `gross_move_usd - gas_cost_usd`.
+    pub net_usd: f64,
+    /// Whether `net_usd` is positive.
+    pub is_profitable: bool,
+}
+
+/// Computes the next block's base fee from a parent block's `base_fee_wei`,
+/// `gas_used`, and `gas_limit`, following EIP-1559's recurrence exactly: the
+/// gas target is half the limit, and the base fee moves toward it by at most
+/// 1/8th per block, proportional to how far `gas_used` is from the target.
+pub fn estimate_next_base_fee(base_fee_wei: u128, gas_used: u64, gas_limit: u64) -> u128 {
+    let gas_target = gas_limit / ELASTICITY_MULTIPLIER;
+
+    if gas_used == gas_target {
+        return base_fee_wei;
+    }
+
+    if gas_used > gas_target {
+        let gas_delta = (gas_used - gas_target) as u128;
+        let delta =
+            (base_fee_wei * gas_delta / gas_target as u128 / BASE_FEE_MAX_CHANGE_DENOMINATOR as u128).max(1);
+        base_fee_wei + delta
+    } else {
+        let gas_delta = (gas_target - gas_used) as u128;
+        let delta = base_fee_wei * gas_delta / gas_target as u128 / BASE_FEE_MAX_CHANGE_DENOMINATOR as u128;
+        base_fee_wei.saturating_sub(delta)
+    }
+}
+
+/// Converts a trade's gas usage into a USD cost. The effective gas price a
+/// trader pays under EIP-1559 is `base_fee_wei + priority_tip_wei`; that's
+/// scaled from wei to whole ETH (`1e-18`) and priced in USD via `eth_price_usd`.
+pub fn effective_trade_cost_usd(
+    gas_units: u64,
+    base_fee_wei: u128,
+    priority_tip_wei: u128,
+    eth_price_usd: f64,
+) -> f64 {
+    let effective_gas_price_wei = base_fee_wei + priority_tip_wei;
+    (gas_units as f64) * (effective_gas_price_wei as f64) * 1e-18 * eth_price_usd
+}
+
+/// Nets a `trade_size_usd` trade's gross move — `pool`'s reported 24h percentage
+/// price change scaled onto the trade size — against the gas cost of spending
+/// `gas_units` at `base_fee_wei` (plus `priority_tip_wei`), so `detect_anomalies`-style
+/// scans can drop dust-level "movers" that don't cover their own gas.
+pub fn trade_profitability(
+    pool: &Pool,
+    trade_size_usd: f64,
+    gas_units: u64,
+    base_fee_wei: u128,
+    priority_tip_wei: u128,
+    eth_price_usd: f64,
+) -> ProfitabilityEstimate {
+    let gross_move_usd = trade_size_usd * (pool.last_price_change_usd_24h / 100.0);
+    let gas_cost_usd = effective_trade_cost_usd(gas_units, base_fee_wei, priority_tip_wei, eth_price_usd);
+    let net_usd = gross_move_usd - gas_cost_usd;
+
+    ProfitabilityEstimate { gross_move_usd, gas_cost_usd, net_usd, is_profitable: net_usd > 0.0 }
+}
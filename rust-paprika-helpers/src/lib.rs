@@ -34,24 +34,71 @@
 pub mod types;
 #[path = "../Docs/internal/error.rs"]
 pub mod error;
+pub mod candles;
+pub mod backfill;
+pub mod cache;
+pub mod coingecko;
+pub mod filters;
+pub mod batch;
+pub mod storage;
+pub mod client_pool;
+pub mod monitor;
+pub mod trend;
+pub mod onchain;
+pub mod scheduler;
+pub mod gas;
+pub mod ranking;
+pub mod snapshot;
+#[cfg(feature = "redis")]
+pub mod price_cache;
 
 pub use types::*;
 pub use error::*;
+pub use candles::{
+    aggregate_candles, aggregate_candles_with_duration, candles_from_transactions,
+    resample_transactions_to_ohlcv,
+};
+pub use backfill::{backfill_pool_ohlcv, backfill_pool_transactions, BackfillProgress};
+pub use cache::{configure_cache_backend, get_cache_backend, Cache, DashMapCache, PostgresCache};
+pub use coingecko::{
+    pools_to_coingecko_tickers, to_coingecko_tickers, write_coingecko_tickers_json, CoinGeckoTicker,
+};
+pub use filters::{
+    create_pool_filter, create_price_change_filter, create_transaction_filter, expire_idle_filters,
+    poll_filter, FilterChanges, FilterId,
+};
+pub use batch::{configure_client, fetch_many, get_client_config, BatchOptions, ClientConfig, RetryPolicy};
+use batch::backoff_with_jitter;
+pub use client_pool::{get_client_pool, ClientPool, PoolMetrics};
+pub use monitor::{async_monitor_prices_broadcast, MonitorConfig, PriceUpdate};
+use monitor::ChangeTracker;
+pub use trend::{record_trend_sample, top_trending, Bucket, TrendMetric, TrendTracker, TrendingPool};
+pub use onchain::{enrich_pools, get_token_metadata, OnchainTokenMetadata, RpcProvider};
+pub use gas::{effective_trade_cost_usd, estimate_next_base_fee, trade_profitability, ProfitabilityEstimate};
+pub use ranking::{rank_pools, top_n, ScoreWeights, ScoredPool};
+pub use snapshot::{MarketSnapshot, MerkleProof, MerkleTree};
+pub use storage::{backfill_resume_point, Storage};
+pub use scheduler::{RequestScheduler, RequestSchedulerBuilder, SchedulerConfig};
+#[cfg(feature = "redis")]
+pub use price_cache::{
+    cache_pool_price, configure_price_cache, get_cached_pool_price, get_price_history, CachedPrice,
+    PriceCacheConfig,
+};
 
 // ============================================================================
 // IMPORTS
 // ============================================================================
 
-use reqwest::Client;
 use serde_json::{Value, json};
 use std::collections::HashMap;
 use std::time::Duration;
 use tokio::time::timeout;
 use url::Url;
-use dashmap::DashMap;
 use chrono::{DateTime, Utc, Timelike};
 use regex::Regex;
 use std::sync::OnceLock;
+use futures::stream::{self, StreamExt};
+use rust_decimal::{Decimal, MathematicalOps};
 
 // ============================================================================
 // CONSTANTS & GLOBALS
@@ -66,27 +113,6 @@ pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
 /// Cache duration for API responses
 pub const CACHE_DURATION: Duration = Duration::from_secs(300); // 5 minutes
 
-/// Global HTTP client
-static CLIENT: OnceLock<Client> = OnceLock::new();
-
-/// Global cache for API responses
-static CACHE: OnceLock<DashMap<String, CacheEntry>> = OnceLock::new();
-
-/// Initialize the global HTTP client
-fn get_client() -> &'static Client {
-    CLIENT.get_or_init(|| {
-        Client::builder()
-            .timeout(DEFAULT_TIMEOUT)
-            .build()
-            .expect("Failed to create HTTP client")
-    })
-}
-
-/// Initialize the global cache
-fn get_cache() -> &'static DashMap<String, CacheEntry> {
-    CACHE.get_or_init(|| DashMap::new())
-}
-
 // ============================================================================
 // PRIVATE HELPER FUNCTIONS
 // ============================================================================
@@ -105,47 +131,95 @@ fn create_cache_key(endpoint: &str, params: &ApiParams) -> String {
 pub async fn api_request(endpoint: &str, params: Option<ApiParams>) -> Result<Value> {
     let params = params.unwrap_or_default();
     let cache_key = create_cache_key(endpoint, &params);
-    
+
     // Check cache first
-    let cache = get_cache();
-    if let Some(entry) = cache.get(&cache_key) {
-        if Utc::now().signed_duration_since(entry.timestamp).num_seconds() < CACHE_DURATION.as_secs() as i64 {
-            return Ok(entry.data.clone());
-        }
+    let cache = get_cache_backend();
+    if let Some(entry) = cache.get(&cache_key).await {
+        return Ok(entry.data.clone());
     }
-    
+
     // Build URL
     let mut url = Url::parse(&format!("{}{}", BASE_URL, endpoint))?;
-    
+
     // Add query parameters
     for (key, value) in params.to_query_params() {
         url.query_pairs_mut().append_pair(key, &value);
     }
-    
-    // Make request with timeout
-    let client = get_client();
-    let response = timeout(DEFAULT_TIMEOUT, client.get(url).send()).await??;
-    
-    // Check for API errors
-    let status = response.status();
-    if !status.is_success() {
-        let error_text = response.text().await?;
-        if let Ok(api_error) = serde_json::from_str::<APIError>(&error_text) {
-            return Err(PaprikaError::ApiError(api_error.error));
+
+    // Send with retry: on 429/5xx/timeout, retry with exponential backoff
+    // plus jitter, honoring any `Retry-After` header (unless disabled via
+    // `ClientConfig::respect_retry_after`).
+    let config = get_client_config();
+    let retry_policy = params.retry_policy.clone().unwrap_or_else(|| config.retry_policy.clone());
+    let request_timeout = params.timeout.unwrap_or(DEFAULT_TIMEOUT);
+    let client = get_client_pool().acquire().await;
+    let mut last_err = PaprikaError::GenericError("request never attempted".to_string());
+
+    for attempt in 0..=retry_policy.max_retries {
+        let send_result = timeout(request_timeout, client.get(url.clone()).send()).await;
+
+        let response = match send_result {
+            Ok(Ok(response)) => response,
+            Ok(Err(e)) => {
+                last_err = PaprikaError::NetworkError(e);
+                if attempt < retry_policy.max_retries {
+                    tokio::time::sleep(backoff_with_jitter(&retry_policy, attempt)).await;
+                    continue;
+                }
+                return Err(last_err);
+            }
+            Err(e) => {
+                last_err = PaprikaError::TimeoutError(e);
+                if attempt < retry_policy.max_retries {
+                    tokio::time::sleep(backoff_with_jitter(&retry_policy, attempt)).await;
+                    continue;
+                }
+                return Err(last_err);
+            }
+        };
+
+        let status = response.status();
+        if !status.is_success() {
+            let retryable = status.as_u16() == 429 || status.is_server_error();
+            let retry_after = if config.respect_retry_after {
+                response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.parse::<u64>().ok())
+                    .map(Duration::from_secs)
+            } else {
+                None
+            };
+
+            let error_text = response.text().await?;
+            last_err = if let Ok(api_error) = serde_json::from_str::<APIError>(&error_text) {
+                PaprikaError::ApiError(api_error.error)
+            } else {
+                PaprikaError::HttpError(status.to_string())
+            };
+
+            if retryable && attempt < retry_policy.max_retries {
+                let delay = retry_after.unwrap_or_else(|| backoff_with_jitter(&retry_policy, attempt));
+                tokio::time::sleep(delay).await;
+                continue;
+            }
+            return Err(last_err);
         }
-        return Err(PaprikaError::HttpError(status.to_string()));
+
+        // Parse JSON response
+        let data: Value = response.json().await?;
+
+        // Store in cache
+        cache.insert(&cache_key, CacheEntry {
+            data: data.clone(),
+            timestamp: Utc::now(),
+        }).await;
+
+        return Ok(data);
     }
-    
-    // Parse JSON response
-    let data: Value = response.json().await?;
-    
-    // Store in cache
-    cache.insert(cache_key, CacheEntry {
-        data: data.clone(),
-        timestamp: Utc::now(),
-    });
-    
-    Ok(data)
+
+    Err(last_err)
 }
 
 /// Retrieves all supported blockchain networks
@@ -588,13 +662,6 @@ pub fn filter_by_token_address(pools: &[Pool], address: &str) -> Vec<Pool> {
         .collect()
 }
 
-/// Returns the top N items by specified field
-pub fn top_n(pools: &[Pool], field: &str, n: usize) -> Vec<Pool> {
-    let mut sorted = sort_by_field(pools, field, true);
-    sorted.truncate(n);
-    sorted
-}
-
 /// Returns the bottom N items by specified field
 pub fn bottom_n(pools: &[Pool], field: &str, n: usize) -> Vec<Pool> {
     let mut sorted = sort_by_field(pools, field, false);
@@ -752,6 +819,60 @@ pub fn calculate_volatility(records: &[OHLCVRecord]) -> f64 {
     variance.sqrt()
 }
 
+/// Volume-weighted average price over raw transactions, computed on the
+/// exact `TokenAmount` (`Decimal`) rather than the integer `volume` field of
+/// `OHLCVRecord` cast to `f64`, so whale-sized trades don't drag in `f64`
+/// rounding error.
+/// Weights each transaction's USD price by `amount_0`.
+pub fn calculate_volume_weighted_price_precise(txs: &[Transaction]) -> Decimal {
+    let mut total_value = Decimal::ZERO;
+    let mut total_amount = Decimal::ZERO;
+
+    for tx in txs {
+        let price = Decimal::try_from((tx.price_0_usd + tx.price_1_usd) / 2.0).unwrap_or(Decimal::ZERO);
+        total_value += price * tx.amount_0.0;
+        total_amount += tx.amount_0.0;
+    }
+
+    if total_amount.is_zero() {
+        Decimal::ZERO
+    } else {
+        total_value / total_amount
+    }
+}
+
+/// Price volatility (standard deviation of returns) over raw transactions,
+/// computed in `Decimal` rather than over `f64` candles. Each price is the
+/// transaction's `price_0_usd`/`price_1_usd` average, converted from `f64`;
+/// the `TokenAmount` fields are not used.
+pub fn calculate_volatility_precise(txs: &[Transaction]) -> Decimal {
+    if txs.len() < 2 {
+        return Decimal::ZERO;
+    }
+
+    let prices: Vec<Decimal> = txs
+        .iter()
+        .map(|tx| Decimal::try_from((tx.price_0_usd + tx.price_1_usd) / 2.0).unwrap_or(Decimal::ZERO))
+        .collect();
+
+    let mut returns = Vec::new();
+    for i in 1..prices.len() {
+        if prices[i - 1] > Decimal::ZERO {
+            returns.push((prices[i] - prices[i - 1]) / prices[i - 1]);
+        }
+    }
+
+    if returns.is_empty() {
+        return Decimal::ZERO;
+    }
+
+    let count = Decimal::from(returns.len() as u64);
+    let mean: Decimal = returns.iter().sum::<Decimal>() / count;
+    let variance: Decimal = returns.iter().map(|r| (*r - mean) * (*r - mean)).sum::<Decimal>() / count;
+
+    variance.sqrt().unwrap_or(Decimal::ZERO)
+}
+
 /// Analyzes pool activity metrics
 pub fn analyze_pool_activity(pool: &Pool) -> HashMap<String, Value> {
     let mut analysis = HashMap::new();
@@ -871,6 +992,94 @@ pub fn detect_anomalies(pools: &[Pool], field: &str, threshold: f64) -> Vec<Anom
     anomalies
 }
 
+/// Computes positional quantiles (min/median/p75/p90/p95/max) of a value series
+pub fn percentiles(values: &[f64]) -> PercentileSummary {
+    if values.is_empty() {
+        return PercentileSummary {
+            min: 0.0,
+            median: 0.0,
+            p75: 0.0,
+            p90: 0.0,
+            p95: 0.0,
+            max: 0.0,
+        };
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let quantile = |q: f64| -> f64 {
+        let idx = (q * (sorted.len() - 1) as f64).round() as usize;
+        sorted[idx.min(sorted.len() - 1)]
+    };
+
+    PercentileSummary {
+        min: sorted[0],
+        median: quantile(0.5),
+        p75: quantile(0.75),
+        p90: quantile(0.90),
+        p95: quantile(0.95),
+        max: sorted[sorted.len() - 1],
+    }
+}
+
+/// Detects anomalies using the median absolute deviation (MAD), which isn't
+/// swamped by the extreme outliers that inflate the mean/std-dev z-score used
+/// by `detect_anomalies`. Flags points where the modified z-score
+/// `0.6745 * (x - median) / MAD` exceeds `threshold` in absolute value
+/// (3.5 is the conventional cutoff). Falls back to the mean absolute deviation when `MAD == 0`
+/// (a common case when more than half the values are identical), and returns
+/// no anomalies if that is also zero.
+pub fn detect_anomalies_robust(pools: &[Pool], field: &str, threshold: f64) -> Vec<AnomalyResult> {
+    let values: Vec<f64> = pools.iter().map(|pool| {
+        match field {
+            "volume_usd" => pool.volume_usd,
+            "price_usd" => pool.price_usd,
+            "transactions" => pool.transactions as f64,
+            "last_price_change_usd_24h" => pool.last_price_change_usd_24h,
+            _ => 0.0,
+        }
+    }).collect();
+
+    if values.len() < 3 {
+        return Vec::new();
+    }
+
+    let mut sorted = values.clone();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let median = sorted[sorted.len() / 2];
+
+    let mut deviations: Vec<f64> = values.iter().map(|v| (v - median).abs()).collect();
+    deviations.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let mad = deviations[deviations.len() / 2];
+
+    let scale = if mad > 0.0 {
+        mad
+    } else {
+        let mean_abs_dev = deviations.iter().sum::<f64>() / deviations.len() as f64;
+        if mean_abs_dev > 0.0 {
+            mean_abs_dev
+        } else {
+            return Vec::new();
+        }
+    };
+
+    let mut anomalies = Vec::new();
+    for (i, &val) in values.iter().enumerate() {
+        let modified_z_score = 0.6745 * (val - median) / scale;
+        if modified_z_score.abs() > threshold {
+            anomalies.push(AnomalyResult {
+                index: i,
+                value: val,
+                z_score: modified_z_score,
+                item: json!(pools[i]),
+            });
+        }
+    }
+
+    anomalies
+}
+
 /// Calculates correlation between two data series
 pub fn calculate_correlation(data1: &[f64], data2: &[f64]) -> f64 {
     if data1.len() != data2.len() || data1.len() < 2 {
@@ -1299,16 +1508,25 @@ pub async fn get_market_overview() -> Result<MarketOverview> {
     let networks = get_networks().await?;
     
     let mut network_overview = HashMap::new();
-    
+    let mut eth_price_usd: Option<f64> = None;
+
     // Analyze top 5 networks
     for (i, network) in networks.iter().enumerate() {
         if i >= 5 {
             break;
         }
-        
+
         if let Ok(pools) = get_network_pools(&network.id, Some(ApiParams::new().limit(10))).await {
             let total_volume: f64 = pools.pools.iter().map(|p| p.volume_usd).sum();
-            
+
+            if network.id == "ethereum" {
+                eth_price_usd = pools
+                    .pools
+                    .iter()
+                    .max_by(|a, b| a.volume_usd.partial_cmp(&b.volume_usd).unwrap_or(std::cmp::Ordering::Equal))
+                    .map(|p| p.price_usd);
+            }
+
             network_overview.insert(network.id.clone(), json!({
                 "display_name": network.display_name,
                 "total_volume": total_volume,
@@ -1316,11 +1534,21 @@ pub async fn get_market_overview() -> Result<MarketOverview> {
             }));
         }
     }
-    
+
+    let sample_swap_gas_cost_usd = eth_price_usd.map(|price| {
+        gas::effective_trade_cost_usd(
+            gas::STANDARD_SWAP_GAS_UNITS,
+            gas::DEFAULT_BASE_FEE_WEI,
+            gas::DEFAULT_PRIORITY_TIP_WEI,
+            price,
+        )
+    });
+
     Ok(MarketOverview {
         system_stats: stats,
         network_overview,
         timestamp: Utc::now().to_rfc3339(),
+        sample_swap_gas_cost_usd,
     })
 }
 
@@ -1334,107 +1562,172 @@ pub async fn async_api_request(endpoint: &str, params: Option<ApiParams>) -> Res
     api_request(endpoint, params).await
 }
 
-/// Gets pools from multiple networks concurrently
-pub async fn async_get_multiple_pools(networks: &[String], limit: u32) -> Result<HashMap<String, Value>> {
-    let mut results = HashMap::new();
-    let mut handles = Vec::new();
-    
-    for network in networks {
-        let net = network.clone();
-        let handle = tokio::spawn(async move {
+/// Gets pools from multiple networks concurrently, driving requests through a
+/// `max_concurrency`-bounded `buffer_unordered` stream instead of unbounded
+/// spawns so a few hundred networks won't hammer the upstream API. Each
+/// request already retries on timeout/5xx/429 inside `api_request`; a final
+/// failure is recorded as an `"error"` entry rather than dropped.
+pub async fn async_get_multiple_pools(
+    networks: &[String],
+    limit: u32,
+    options: Option<BatchOptions>,
+) -> Result<HashMap<String, Value>> {
+    let options = options.unwrap_or_default();
+
+    let results: Vec<(String, Result<Value>)> = stream::iter(networks.iter().cloned())
+        .map(|network| async move {
             let params = ApiParams::new().limit(limit);
-            (net.clone(), api_request(&format!("/networks/{}/pools", net), Some(params)).await)
-        });
-        handles.push(handle);
-    }
-    
-    for handle in handles {
-        if let Ok((network, result)) = handle.await {
-            match result {
-                Ok(data) => {
-                    results.insert(network, data);
-                }
-                Err(e) => {
-                    results.insert(network, json!({"error": e.to_string()}));
-                }
+            let endpoint = format!("/networks/{}/pools", network);
+            let result = api_request(&endpoint, Some(params)).await;
+            (network, result)
+        })
+        .buffer_unordered(options.max_concurrency)
+        .collect()
+        .await;
+
+    let mut by_network = HashMap::new();
+    for (network, result) in results {
+        match result {
+            Ok(data) => {
+                by_network.insert(network, data);
+            }
+            Err(e) => {
+                by_network.insert(network, json!({"error": e.to_string()}));
             }
         }
     }
-    
-    Ok(results)
+
+    Ok(by_network)
 }
 
-/// Gets data for multiple tokens concurrently
-pub async fn async_get_token_data_batch(token_addresses: &[String], network: &str) -> Result<Vec<AsyncResult<Token>>> {
-    let mut handles = Vec::new();
-    
-    for (index, address) in token_addresses.iter().enumerate() {
-        let addr = address.clone();
-        let net = network.to_string();
-        let handle = tokio::spawn(async move {
-            let endpoint = format!("/networks/{}/tokens/{}", net, addr);
-            let result = api_request(&endpoint, None).await;
-            (index, result)
-        });
-        handles.push(handle);
-    }
-    
-    let mut results = vec![AsyncResult { network: None, query: None, data: None, error: None }; token_addresses.len()];
-    
-    for handle in handles {
-        if let Ok((index, result)) = handle.await {
-            match result {
-                Ok(data) => {
-                    if let Ok(token) = serde_json::from_value::<Token>(data) {
-                        results[index] = AsyncResult {
-                            network: Some(network.to_string()),
-                            query: None,
-                            data: Some(token),
-                            error: None,
-                        };
-                    }
-                }
-                Err(e) => {
-                    results[index] = AsyncResult {
-                        network: Some(network.to_string()),
-                        query: None,
-                        data: None,
-                        error: Some(e.to_string()),
-                    };
-                }
+/// Gets data for multiple tokens concurrently, bounded by `options.max_concurrency`.
+pub async fn async_get_token_data_batch(
+    token_addresses: &[String],
+    network: &str,
+    options: Option<BatchOptions>,
+) -> Result<Vec<AsyncResult<Token>>> {
+    let options = options.unwrap_or_default();
+
+    let indexed: Vec<(usize, Result<Value>)> = stream::iter(token_addresses.iter().cloned().enumerate())
+        .map(|(index, address)| {
+            let network = network.to_string();
+            async move {
+                let endpoint = format!("/networks/{}/tokens/{}", network, address);
+                (index, api_request(&endpoint, None).await)
             }
-        }
+        })
+        .buffer_unordered(options.max_concurrency)
+        .collect()
+        .await;
+
+    let mut results = vec![AsyncResult { network: None, query: None, data: None, error: None }; token_addresses.len()];
+
+    for (index, result) in indexed {
+        results[index] = match result {
+            Ok(data) => match serde_json::from_value::<Token>(data) {
+                Ok(token) => AsyncResult {
+                    network: Some(network.to_string()),
+                    query: None,
+                    data: Some(token),
+                    error: None,
+                },
+                Err(e) => AsyncResult {
+                    network: Some(network.to_string()),
+                    query: None,
+                    data: None,
+                    error: Some(e.to_string()),
+                },
+            },
+            Err(e) => AsyncResult {
+                network: Some(network.to_string()),
+                query: None,
+                data: None,
+                error: Some(e.to_string()),
+            },
+        };
     }
-    
+
     Ok(results)
 }
 
-/// Monitors pool prices with callback
+/// Monitors pool prices with callback. When the `redis` feature is enabled
+/// and a pool has a cached snapshot within its TTL, the cached value is used
+/// instead of calling `get_pool_details` again; otherwise the pool is
+/// fetched and (if the feature is enabled) the result is cached for the next
+/// tick and for other tasks to read via `get_cached_pool_price`. When
+/// `monitor_config` is set, the callback only fires once a pool's
+/// `price_usd` clears its threshold/dedup rules (see `MonitorConfig`);
+/// `None` preserves the prior unconditional behavior.
 pub async fn async_monitor_prices<F>(
-    pool_addresses: &[String], 
-    network: &str, 
-    interval: Duration, 
+    pool_addresses: &[String],
+    network: &str,
+    interval: Duration,
+    monitor_config: Option<MonitorConfig>,
     mut callback: F
-) -> Result<()> 
-where 
+) -> Result<()>
+where
     F: FnMut(&str, HashMap<String, Value>) + Send + 'static,
 {
     let mut interval_timer = tokio::time::interval(interval);
-    
+    let tracker = ChangeTracker::new();
+    let monitor_config = monitor_config.unwrap_or_default();
+
     loop {
         interval_timer.tick().await;
-        
+
         for address in pool_addresses {
             let network_clone = network.to_string();
             let address_clone = address.clone();
-            
+
+            #[cfg(feature = "redis")]
+            {
+                if let Ok(Some(cached)) = price_cache::get_cached_pool_price(&address_clone).await {
+                    record_trend_sample(&address_clone, cached.price_usd, cached.volume_usd, Utc::now());
+
+                    if !tracker.should_emit(&monitor_config, &address_clone, cached.price_usd) {
+                        continue;
+                    }
+
+                    let mut price_update = HashMap::new();
+                    price_update.insert("price_usd".to_string(), json!(cached.price_usd));
+                    price_update.insert(
+                        "last_price_change_usd_24h".to_string(),
+                        json!(cached.last_price_change_usd_24h),
+                    );
+                    price_update.insert("volume_usd".to_string(), json!(cached.volume_usd));
+                    price_update.insert("timestamp".to_string(), json!(cached.timestamp));
+
+                    callback(&address_clone, price_update);
+                    continue;
+                }
+            }
+
             if let Ok(pool) = get_pool_details(&network_clone, &address_clone, None).await {
+                let now = Utc::now();
+                let timestamp = now.to_rfc3339();
+                record_trend_sample(&address_clone, pool.price_usd, pool.volume_usd, now);
+
+                #[cfg(feature = "redis")]
+                {
+                    let snapshot = price_cache::CachedPrice {
+                        price_usd: pool.price_usd,
+                        last_price_change_usd_24h: pool.last_price_change_usd_24h,
+                        volume_usd: pool.volume_usd,
+                        timestamp: timestamp.clone(),
+                    };
+                    let _ = price_cache::cache_pool_price(&address_clone, &snapshot).await;
+                }
+
+                if !tracker.should_emit(&monitor_config, &address_clone, pool.price_usd) {
+                    continue;
+                }
+
                 let mut price_update = HashMap::new();
                 price_update.insert("price_usd".to_string(), json!(pool.price_usd));
                 price_update.insert("last_price_change_usd_24h".to_string(), json!(pool.last_price_change_usd_24h));
                 price_update.insert("volume_usd".to_string(), json!(pool.volume_usd));
-                price_update.insert("timestamp".to_string(), json!(Utc::now().to_rfc3339()));
-                
+                price_update.insert("timestamp".to_string(), json!(timestamp));
+
                 callback(&address_clone, price_update);
             }
         }
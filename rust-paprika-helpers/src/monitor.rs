@@ -0,0 +1,143 @@
+//! Change-threshold filtering and push delivery for `async_monitor_prices`
+//!
+//! `MonitorConfig` lets callers require a price move past an
+//! absolute/percentage threshold (with a per-pool dedup window), via the
+//! internal `ChangeTracker`, before an update fires — rather than firing on
+//! every tick. `async_monitor_prices` accepts it for its callback;
+//! `async_monitor_prices_broadcast`
+//! applies the same filtering but pushes updates over a `broadcast` channel,
+//! so multiple subscribers (e.g. a WebSocket or SSE endpoint) can share one
+//! feed.
+
+use crate::{get_pool_details, Result};
+use chrono::Utc;
+use dashmap::DashMap;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// One filtered price update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceUpdate {
+    pub pool_address: String,
+    pub price_usd: f64,
+    pub last_price_change_usd_24h: f64,
+    pub volume_usd: f64,
+    pub timestamp: String,
+}
+
+/// Change-threshold filtering for the price monitor. All thresholds default
+/// to `0.0`/zero duration, i.e. every tick is emitted — matching
+/// `async_monitor_prices`'s prior unconditional behavior.
+#[derive(Debug, Clone)]
+pub struct MonitorConfig {
+    /// Minimum absolute `price_usd` delta since the last emitted value
+    /// required to fire an update. `0.0` disables the absolute threshold.
+    pub min_absolute_change: f64,
+    /// Minimum percentage `price_usd` delta since the last emitted value
+    /// required to fire an update. `0.0` disables the percentage threshold.
+    pub min_percent_change: f64,
+    /// Minimum time that must elapse since a pool's last emitted update
+    /// before another update for it can fire, regardless of price move size.
+    pub dedup_window: Duration,
+}
+
+impl Default for MonitorConfig {
+    fn default() -> Self {
+        Self {
+            min_absolute_change: 0.0,
+            min_percent_change: 0.0,
+            dedup_window: Duration::from_secs(0),
+        }
+    }
+}
+
+/// Tracks, per pool, the last emitted price and when it was emitted, so
+/// `should_emit` can apply `MonitorConfig`'s threshold/dedup rules across
+/// ticks.
+pub(crate) struct ChangeTracker {
+    last_emitted: DashMap<String, (f64, Instant)>,
+}
+
+impl ChangeTracker {
+    pub(crate) fn new() -> Self {
+        Self { last_emitted: DashMap::new() }
+    }
+
+    /// Returns whether `price_usd` for `pool_address` clears `config`'s
+    /// threshold/dedup window, recording it as the new last-emitted value if
+    /// so.
+    pub(crate) fn should_emit(&self, config: &MonitorConfig, pool_address: &str, price_usd: f64) -> bool {
+        let now = Instant::now();
+
+        if let Some(entry) = self.last_emitted.get(pool_address) {
+            let (last_price, last_emitted_at) = *entry;
+            if now.duration_since(last_emitted_at) < config.dedup_window {
+                return false;
+            }
+
+            let delta = (price_usd - last_price).abs();
+            let percent = if last_price != 0.0 { (delta / last_price.abs()) * 100.0 } else { 0.0 };
+
+            // A threshold of `0.0` is disabled; with any threshold enabled,
+            // clearing at least one enabled threshold fires an update.
+            let absolute_enabled = config.min_absolute_change > 0.0;
+            let percent_enabled = config.min_percent_change > 0.0;
+            let clears_absolute = absolute_enabled && delta >= config.min_absolute_change;
+            let clears_percent = percent_enabled && percent >= config.min_percent_change;
+
+            if (absolute_enabled || percent_enabled) && !clears_absolute && !clears_percent {
+                return false;
+            }
+        }
+
+        self.last_emitted.insert(pool_address.to_string(), (price_usd, now));
+        true
+    }
+}
+
+/// Monitors pool prices, pushing `config`-filtered updates to a `broadcast`
+/// channel instead of an `FnMut` callback, so multiple subscribers can
+/// consume the same feed (e.g. to back a WebSocket or SSE endpoint). The
+/// returned `Sender` can be `.subscribe()`d to as many times as needed. The
+/// monitor runs on a detached task whose handle is not returned, so it keeps
+/// polling (even with no subscribers) until the Tokio runtime shuts down.
+pub async fn async_monitor_prices_broadcast(
+    pool_addresses: &[String],
+    network: &str,
+    interval: Duration,
+    config: MonitorConfig,
+) -> Result<broadcast::Sender<PriceUpdate>> {
+    let (sender, _) = broadcast::channel(256);
+    let task_sender = sender.clone();
+    let pool_addresses = pool_addresses.to_vec();
+    let network = network.to_string();
+
+    tokio::spawn(async move {
+        let tracker = ChangeTracker::new();
+        let mut interval_timer = tokio::time::interval(interval);
+
+        loop {
+            interval_timer.tick().await;
+
+            for address in &pool_addresses {
+                if let Ok(pool) = get_pool_details(&network, address, None).await {
+                    if !tracker.should_emit(&config, address, pool.price_usd) {
+                        continue;
+                    }
+
+                    let update = PriceUpdate {
+                        pool_address: address.clone(),
+                        price_usd: pool.price_usd,
+                        last_price_change_usd_24h: pool.last_price_change_usd_24h,
+                        volume_usd: pool.volume_usd,
+                        timestamp: Utc::now().to_rfc3339(),
+                    };
+
+                    let _ = task_sender.send(update);
+                }
+            }
+        }
+    });
+
+    Ok(sender)
+}
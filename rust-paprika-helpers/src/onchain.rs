@@ -0,0 +1,332 @@
+//! On-chain ERC-20/SPL token metadata enrichment
+//!
+//! `enrich_pools` reads `decimals()`, `symbol()`, `name()`, and
+//! `totalSupply()` straight off the chain — via a JSON-RPC `RpcProvider` for
+//! EVM chains, or an SPL mint-account decode for Solana, routed by the same
+//! `0x...`/base58 address shape `validate_token_address` already
+//! distinguishes — and merges the result into each pool's tokens.
+//!
+//! An ERC-20 read is an `eth_call` with `to = token` and `data` = one of the
+//! fixed 4-byte selectors below; none of the four methods take arguments, so
+//! no ABI argument encoding is needed. `decimals`/`totalSupply` decode as a
+//! big-endian `uint256`; `symbol`/`name` decode as ABI dynamic strings
+//! (offset word, length word, then UTF-8 bytes). Solana mint accounts decode
+//! per the SPL token program's fixed `Mint` layout, which carries
+//! `supply`/`decimals` but not a name/symbol — those live in a separate
+//! metadata program, out of scope here.
+
+use crate::{PaprikaError, Pool, Result};
+use dashmap::DashMap;
+use futures::stream::{self, StreamExt};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+const SELECTOR_DECIMALS: &str = "0x313ce567";
+const SELECTOR_SYMBOL: &str = "0x95d89b41";
+const SELECTOR_NAME: &str = "0x06fdde03";
+const SELECTOR_TOTAL_SUPPLY: &str = "0x18160ddd";
+
+/// Maps each chain to the JSON-RPC endpoints used to read it, tried in
+/// order until one responds.
+#[derive(Debug, Clone, Default)]
+pub struct RpcProvider {
+    pub endpoints: HashMap<String, Vec<String>>,
+}
+
+impl RpcProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `url` as an endpoint for `chain`, tried after any
+    /// endpoints already registered for it.
+    pub fn with_endpoint(mut self, chain: &str, url: &str) -> Self {
+        self.endpoints.entry(chain.to_string()).or_default().push(url.to_string());
+        self
+    }
+}
+
+/// On-chain metadata for one token, read directly from the contract rather
+/// than trusted from the API response.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OnchainTokenMetadata {
+    pub decimals: u8,
+    pub symbol: String,
+    pub name: String,
+    pub total_supply: u128,
+}
+
+static METADATA_CACHE: OnceLock<DashMap<(String, String), OnchainTokenMetadata>> = OnceLock::new();
+
+fn metadata_cache() -> &'static DashMap<(String, String), OnchainTokenMetadata> {
+    METADATA_CACHE.get_or_init(DashMap::new)
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcResponse {
+    result: Option<String>,
+    error: Option<JsonRpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JsonRpcError {
+    message: String,
+}
+
+/// Issues `eth_call(to, data)` against `chain`'s endpoints in order,
+/// returning the first successful result.
+async fn eth_call(provider: &RpcProvider, chain: &str, to: &str, data: &str) -> Result<String> {
+    let endpoints = provider
+        .endpoints
+        .get(chain)
+        .ok_or_else(|| PaprikaError::GenericError(format!("no RPC endpoint configured for chain '{chain}'")))?;
+
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "eth_call",
+        "params": [{"to": to, "data": data}, "latest"],
+    });
+
+    let mut last_err = PaprikaError::GenericError(format!("no RPC endpoint reachable for chain '{chain}'"));
+    for url in endpoints {
+        let client = crate::get_client_pool().acquire().await;
+        let response = match client.post(url.as_str()).json(&body).send().await {
+            Ok(response) => response,
+            Err(e) => {
+                last_err = PaprikaError::NetworkError(e);
+                continue;
+            }
+        };
+
+        let parsed: JsonRpcResponse = match response.json().await {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                last_err = PaprikaError::NetworkError(e);
+                continue;
+            }
+        };
+
+        if let Some(error) = parsed.error {
+            last_err = PaprikaError::ApiError(error.message);
+            continue;
+        }
+
+        if let Some(result) = parsed.result {
+            return Ok(result);
+        }
+    }
+
+    Err(last_err)
+}
+
+fn decode_uint256(hex_result: &str) -> Result<u128> {
+    let hex_result = hex_result.trim_start_matches("0x");
+    let bytes = hex_digits_to_bytes(hex_result)?;
+    // A uint256 can exceed u128, but decimals/totalSupply fit in practice;
+    // take the low 16 bytes (big-endian tail) as the value.
+    let tail = &bytes[bytes.len().saturating_sub(16)..];
+    let mut value: u128 = 0;
+    for byte in tail {
+        value = (value << 8) | *byte as u128;
+    }
+    Ok(value)
+}
+
+fn decode_abi_string(hex_result: &str) -> Result<String> {
+    let hex_result = hex_result.trim_start_matches("0x");
+    let bytes = hex_digits_to_bytes(hex_result)?;
+    if bytes.len() < 64 {
+        return Err(PaprikaError::GenericError("ABI string return too short".to_string()));
+    }
+
+    let length = u32::from_be_bytes(bytes[60..64].try_into().unwrap()) as usize;
+    let data_start = 64;
+    let data_end = data_start + length;
+    if bytes.len() < data_end {
+        return Err(PaprikaError::GenericError("ABI string return truncated".to_string()));
+    }
+
+    String::from_utf8(bytes[data_start..data_end].to_vec())
+        .map_err(|e| PaprikaError::GenericError(e.to_string()))
+}
+
+fn hex_digits_to_bytes(hex: &str) -> Result<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return Err(PaprikaError::GenericError("odd-length hex string".to_string()));
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|e| PaprikaError::GenericError(e.to_string())))
+        .collect()
+}
+
+/// Reads `decimals`/`symbol`/`name`/`totalSupply` for an EVM token address
+/// via four `eth_call`s against `provider`.
+async fn fetch_erc20_metadata(provider: &RpcProvider, chain: &str, address: &str) -> Result<OnchainTokenMetadata> {
+    let decimals_result = eth_call(provider, chain, address, SELECTOR_DECIMALS).await?;
+    let symbol_result = eth_call(provider, chain, address, SELECTOR_SYMBOL).await?;
+    let name_result = eth_call(provider, chain, address, SELECTOR_NAME).await?;
+    let supply_result = eth_call(provider, chain, address, SELECTOR_TOTAL_SUPPLY).await?;
+
+    Ok(OnchainTokenMetadata {
+        decimals: decode_uint256(&decimals_result)? as u8,
+        symbol: decode_abi_string(&symbol_result)?,
+        name: decode_abi_string(&name_result)?,
+        total_supply: decode_uint256(&supply_result)?,
+    })
+}
+
+/// Decodes an SPL `Mint` account's `supply`/`decimals` from its raw
+/// (already base64-decoded) account data. Symbol/name aren't part of the mint account itself (they
+/// live in a separate metadata program), so they're left empty.
+fn decode_spl_mint(data: &[u8]) -> Result<OnchainTokenMetadata> {
+    // Mint layout: mint_authority COption<Pubkey> (36 bytes) | supply u64 LE
+    // (8 bytes) | decimals u8 (1 byte) | ...
+    if data.len() < 45 {
+        return Err(PaprikaError::GenericError("SPL mint account data too short".to_string()));
+    }
+
+    let supply = u64::from_le_bytes(data[36..44].try_into().unwrap());
+    let decimals = data[44];
+
+    Ok(OnchainTokenMetadata {
+        decimals,
+        symbol: String::new(),
+        name: String::new(),
+        total_supply: supply as u128,
+    })
+}
+
+/// Reads an SPL mint account's metadata via `getAccountInfo`.
+async fn fetch_spl_mint_metadata(provider: &RpcProvider, chain: &str, address: &str) -> Result<OnchainTokenMetadata> {
+    let endpoints = provider
+        .endpoints
+        .get(chain)
+        .ok_or_else(|| PaprikaError::GenericError(format!("no RPC endpoint configured for chain '{chain}'")))?;
+
+    let body = json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getAccountInfo",
+        "params": [address, {"encoding": "base64"}],
+    });
+
+    let mut last_err = PaprikaError::GenericError(format!("no RPC endpoint reachable for chain '{chain}'"));
+    for url in endpoints {
+        let client = crate::get_client_pool().acquire().await;
+        let response: Value = match client.post(url.as_str()).json(&body).send().await {
+            Ok(response) => match response.json().await {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    last_err = PaprikaError::NetworkError(e);
+                    continue;
+                }
+            },
+            Err(e) => {
+                last_err = PaprikaError::NetworkError(e);
+                continue;
+            }
+        };
+
+        let Some(base64_data) = response["result"]["value"]["data"][0].as_str() else {
+            last_err = PaprikaError::GenericError("missing account data in getAccountInfo response".to_string());
+            continue;
+        };
+
+        let decoded = base64_decode(base64_data)?;
+        return decode_spl_mint(&decoded);
+    }
+
+    Err(last_err)
+}
+
+fn base64_decode(input: &str) -> Result<Vec<u8>> {
+    const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let input = input.trim_end_matches('=');
+    let mut bits = 0u32;
+    let mut bit_count = 0u32;
+    let mut out = Vec::with_capacity(input.len() * 3 / 4);
+
+    for c in input.bytes() {
+        let value = ALPHABET
+            .iter()
+            .position(|&b| b == c)
+            .ok_or_else(|| PaprikaError::GenericError("invalid base64 character".to_string()))? as u32;
+        bits = (bits << 6) | value;
+        bit_count += 6;
+        if bit_count >= 8 {
+            bit_count -= 8;
+            out.push((bits >> bit_count) as u8);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Looks up (and caches) on-chain metadata for `address` on `chain`,
+/// routing `0x`-prefixed addresses to the ERC-20 `eth_call` path and
+/// everything else to the SPL mint decode.
+pub async fn get_token_metadata(provider: &RpcProvider, chain: &str, address: &str) -> Result<OnchainTokenMetadata> {
+    let cache_key = (chain.to_string(), address.to_string());
+    if let Some(cached) = metadata_cache().get(&cache_key) {
+        return Ok(cached.clone());
+    }
+
+    let metadata = if address.starts_with("0x") {
+        fetch_erc20_metadata(provider, chain, address).await?
+    } else {
+        fetch_spl_mint_metadata(provider, chain, address).await?
+    };
+
+    metadata_cache().insert(cache_key, metadata.clone());
+    Ok(metadata)
+}
+
+/// Enriches `pools`' tokens with on-chain metadata, fetched concurrently
+/// (and cached per address) via `provider`. A token whose metadata fails to
+/// resolve is left as returned by the API rather than failing the batch.
+pub async fn enrich_pools(pools: &[Pool], provider: &RpcProvider) -> Result<Vec<Pool>> {
+    let mut enriched: Vec<Pool> = pools.to_vec();
+
+    let lookups: Vec<(usize, usize, String, String)> = enriched
+        .iter()
+        .enumerate()
+        .flat_map(|(pool_idx, pool)| {
+            pool.tokens
+                .iter()
+                .enumerate()
+                .map(move |(token_idx, token)| (pool_idx, token_idx, pool.chain.clone(), token.id.clone()))
+        })
+        .collect();
+
+    let results: Vec<(usize, usize, Result<OnchainTokenMetadata>)> = stream::iter(lookups)
+        .map(|(pool_idx, token_idx, chain, address)| {
+            let provider = provider.clone();
+            async move {
+                let metadata = get_token_metadata(&provider, &chain, &address).await;
+                (pool_idx, token_idx, metadata)
+            }
+        })
+        .buffer_unordered(8)
+        .collect()
+        .await;
+
+    for (pool_idx, token_idx, metadata) in results {
+        if let Ok(metadata) = metadata {
+            let token = &mut enriched[pool_idx].tokens[token_idx];
+            token.decimals = metadata.decimals as u32;
+            if !metadata.symbol.is_empty() {
+                token.symbol = metadata.symbol;
+            }
+            if !metadata.name.is_empty() {
+                token.name = metadata.name;
+            }
+            token.total_supply = metadata.total_supply as f64;
+        }
+    }
+
+    Ok(enriched)
+}
@@ -0,0 +1,135 @@
+//! Redis-backed price cache and short-term history for `async_monitor_prices`
+//!
+//! Gated behind the `redis` feature. Persists each tick's snapshot with a
+//! pipelined `SET` + `EXPIRE` so stale pools self-evict, plus a capped
+//! per-pool sorted set of recent snapshots for short-term history — both
+//! shared through one `redis::aio::ConnectionManager` so the monitor (and
+//! other tasks) survive restarts and read last-known prices without
+//! re-hitting the API.
+
+use crate::{PaprikaError, Result};
+use chrono::Utc;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+static PRICE_CACHE_CONFIG: OnceLock<PriceCacheConfig> = OnceLock::new();
+static CONNECTION: OnceCell<ConnectionManager> = OnceCell::const_new();
+
+/// Configures the Redis-backed price cache. Takes effect only if passed to
+/// `configure_price_cache` before the first `cache_pool_price`/
+/// `get_cached_pool_price` call; otherwise `PriceCacheConfig::default()` is
+/// used.
+#[derive(Debug, Clone)]
+pub struct PriceCacheConfig {
+    pub redis_url: String,
+    /// How long a cached snapshot stays valid before Redis evicts it.
+    pub snapshot_ttl: Duration,
+    /// Maximum number of historical snapshots retained per pool.
+    pub history_capacity: isize,
+}
+
+impl Default for PriceCacheConfig {
+    fn default() -> Self {
+        Self {
+            redis_url: "redis://127.0.0.1/".to_string(),
+            snapshot_ttl: Duration::from_secs(60),
+            history_capacity: 500,
+        }
+    }
+}
+
+/// One cached price snapshot for a pool.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedPrice {
+    pub price_usd: f64,
+    pub last_price_change_usd_24h: f64,
+    pub volume_usd: f64,
+    pub timestamp: String,
+}
+
+/// Injects the `PriceCacheConfig` used by the rest of this module. Later
+/// calls are ignored.
+pub fn configure_price_cache(config: PriceCacheConfig) {
+    let _ = PRICE_CACHE_CONFIG.set(config);
+}
+
+fn get_price_cache_config() -> &'static PriceCacheConfig {
+    PRICE_CACHE_CONFIG.get_or_init(PriceCacheConfig::default)
+}
+
+async fn connection() -> Result<ConnectionManager> {
+    if let Some(conn) = CONNECTION.get() {
+        return Ok(conn.clone());
+    }
+
+    let config = get_price_cache_config();
+    let client = redis::Client::open(config.redis_url.clone())
+        .map_err(|e| PaprikaError::GenericError(e.to_string()))?;
+    let manager = client
+        .get_tokio_connection_manager()
+        .await
+        .map_err(|e| PaprikaError::GenericError(e.to_string()))?;
+
+    Ok(CONNECTION.get_or_init(|| async { manager }).await.clone())
+}
+
+fn snapshot_key(address: &str) -> String {
+    format!("paprika:price:{address}")
+}
+
+fn history_key(address: &str) -> String {
+    format!("paprika:price_history:{address}")
+}
+
+/// Writes `price` for `address`: a pipelined `SET` with TTL on the snapshot
+/// key, plus an append to `address`'s capped history sorted set.
+pub async fn cache_pool_price(address: &str, price: &CachedPrice) -> Result<()> {
+    let config = get_price_cache_config();
+    let mut conn = connection().await?;
+    let payload = serde_json::to_string(price)?;
+    let score = Utc::now().timestamp_millis();
+
+    redis::pipe()
+        .atomic()
+        .set_ex(snapshot_key(address), &payload, config.snapshot_ttl.as_secs())
+        .zadd(history_key(address), &payload, score)
+        .zremrangebyrank(history_key(address), 0, -(config.history_capacity + 1))
+        .query_async(&mut conn)
+        .await
+        .map_err(|e| PaprikaError::GenericError(e.to_string()))?;
+
+    Ok(())
+}
+
+/// Returns the last cached price for `address`, if a snapshot is present and
+/// hasn't expired past its TTL.
+pub async fn get_cached_pool_price(address: &str) -> Result<Option<CachedPrice>> {
+    let mut conn = connection().await?;
+    let payload: Option<String> = conn
+        .get(snapshot_key(address))
+        .await
+        .map_err(|e| PaprikaError::GenericError(e.to_string()))?;
+
+    payload.map(|p| serde_json::from_str(&p).map_err(PaprikaError::from)).transpose()
+}
+
+/// Returns `address`'s cached price history within the trailing `window`.
+pub async fn get_price_history(address: &str, window: Duration) -> Result<Vec<CachedPrice>> {
+    let mut conn = connection().await?;
+    let now = Utc::now().timestamp_millis();
+    let since = now - window.as_millis() as i64;
+
+    let payloads: Vec<String> = conn
+        .zrangebyscore(history_key(address), since, now)
+        .await
+        .map_err(|e| PaprikaError::GenericError(e.to_string()))?;
+
+    payloads
+        .iter()
+        .map(|p| serde_json::from_str(p).map_err(PaprikaError::from))
+        .collect()
+}
@@ -0,0 +1,146 @@
+//! Composable multi-factor pool ranking
+//!
+//! `rank_pools` z-score normalizes several competing signals across the
+//! input set (volume, transaction count, 24h price change, activity,
+//! recency) and combines them into one weighted score, so callers can tune
+//! "hot pool" detection — e.g. weight recency and price change for
+//! momentum, volume and transactions for liquidity — instead of sorting on
+//! one fixed field. `top_n` is a thin single-weight shim over `rank_pools`
+//! for callers that only want the old field-name behavior.
+
+use crate::{PaprikaError, Pool, Result};
+use chrono::DateTime;
+
+/// Per-factor weights driving `rank_pools`'s combined score. A factor left at
+/// `0.0` doesn't affect the ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ScoreWeights {
+    pub volume: f64,
+    pub transactions: f64,
+    pub price_change_24h: f64,
+    pub activity: f64,
+    pub recency: f64,
+}
+
+/// One pool's combined score plus its per-factor contributions (each already
+/// scaled by its weight), so callers can see what drove the ranking.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScoredPool {
+    pub pool: Pool,
+    pub score: f64,
+    pub volume_contribution: f64,
+    pub transactions_contribution: f64,
+    pub price_change_24h_contribution: f64,
+    pub activity_contribution: f64,
+    pub recency_contribution: f64,
+}
+
+/// Z-score normalizes `values` (mean 0, unit variance). An all-equal input
+/// (zero variance) normalizes to all zeros rather than dividing by zero.
+fn z_scores(values: &[f64]) -> Vec<f64> {
+    let count = values.len() as f64;
+    if count == 0.0 {
+        return Vec::new();
+    }
+
+    let mean = values.iter().sum::<f64>() / count;
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+    let std_dev = variance.sqrt();
+
+    if std_dev == 0.0 {
+        return vec![0.0; values.len()];
+    }
+
+    values.iter().map(|v| (v - mean) / std_dev).collect()
+}
+
+/// Same activity scoring `analyze_pool_activity` reports, reused here as a
+/// ranking factor.
+fn activity_score(pool: &Pool) -> f64 {
+    (pool.volume_usd + 1.0).log10() * (pool.transactions as f64 + 1.0).log10()
+}
+
+/// How recently a pool was created, as a Unix timestamp; unparseable
+/// `created_at` values score as the epoch, ranking last.
+fn recency_score(pool: &Pool) -> f64 {
+    DateTime::parse_from_rfc3339(&pool.created_at).map(|dt| dt.timestamp() as f64).unwrap_or(0.0)
+}
+
+/// Ranks `pools` by a weighted combination of z-score-normalized factors,
+/// returning the top `n` with their per-factor contributions attached.
+pub fn rank_pools(pools: &[Pool], weights: ScoreWeights, n: usize) -> Vec<ScoredPool> {
+    if pools.is_empty() {
+        return Vec::new();
+    }
+
+    let volume_z = z_scores(&pools.iter().map(|p| p.volume_usd).collect::<Vec<_>>());
+    let transactions_z = z_scores(&pools.iter().map(|p| p.transactions as f64).collect::<Vec<_>>());
+    let price_change_24h_z = z_scores(&pools.iter().map(|p| p.last_price_change_usd_24h).collect::<Vec<_>>());
+    let activity_z = z_scores(&pools.iter().map(activity_score).collect::<Vec<_>>());
+    let recency_z = z_scores(&pools.iter().map(recency_score).collect::<Vec<_>>());
+
+    let mut scored: Vec<ScoredPool> = pools
+        .iter()
+        .enumerate()
+        .map(|(i, pool)| {
+            let volume_contribution = volume_z[i] * weights.volume;
+            let transactions_contribution = transactions_z[i] * weights.transactions;
+            let price_change_24h_contribution = price_change_24h_z[i] * weights.price_change_24h;
+            let activity_contribution = activity_z[i] * weights.activity;
+            let recency_contribution = recency_z[i] * weights.recency;
+
+            let score = volume_contribution
+                + transactions_contribution
+                + price_change_24h_contribution
+                + activity_contribution
+                + recency_contribution;
+
+            ScoredPool {
+                pool: pool.clone(),
+                score,
+                volume_contribution,
+                transactions_contribution,
+                price_change_24h_contribution,
+                activity_contribution,
+                recency_contribution,
+            }
+        })
+        .collect();
+
+    scored.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+    scored.truncate(n);
+    scored
+}
+
+/// Back-compat shim for the old single-field `top_n`: maps a known field name
+/// onto a single-weight `ScoreWeights`, then unwraps `rank_pools`'s
+/// `ScoredPool`s back down to plain `Pool`s. Returns a `ValidationError` for
+/// an unrecognized field instead of silently sorting by nothing.
+///
+/// `price_usd` was supported by the original field-sorting `top_n` but isn't
+/// one of `rank_pools`'s five factors, so it's handled as a direct raw-value
+/// sort rather than going through `ScoreWeights`.
+pub fn top_n(pools: &[Pool], field: &str, n: usize) -> Result<Vec<Pool>> {
+    if field == "price_usd" {
+        let mut sorted = pools.to_vec();
+        sorted.sort_by(|a, b| b.price_usd.partial_cmp(&a.price_usd).unwrap_or(std::cmp::Ordering::Equal));
+        sorted.truncate(n);
+        return Ok(sorted);
+    }
+
+    let weights = match field {
+        "volume_usd" => ScoreWeights { volume: 1.0, ..Default::default() },
+        "transactions" => ScoreWeights { transactions: 1.0, ..Default::default() },
+        "last_price_change_usd_24h" => ScoreWeights { price_change_24h: 1.0, ..Default::default() },
+        "activity_score" => ScoreWeights { activity: 1.0, ..Default::default() },
+        "created_at" => ScoreWeights { recency: 1.0, ..Default::default() },
+        other => {
+            return Err(PaprikaError::ValidationError(format!(
+                "unknown top_n field '{other}' — expected one of: volume_usd, price_usd, transactions, \
+                 last_price_change_usd_24h, activity_score, created_at"
+            )))
+        }
+    };
+
+    Ok(rank_pools(pools, weights, n).into_iter().map(|scored| scored.pool).collect())
+}
@@ -0,0 +1,172 @@
+//! Debounced auto-batching scheduler in front of `api_request`
+//!
+//! Callers `schedule` a request and get back a future for their individual
+//! result, while a background worker coalesces requests that arrive within
+//! `debounce_duration` of each other into batches and dispatches them
+//! concurrently — instead of `async_batch_search`-style fan-out that fires
+//! one request per query immediately.
+
+use crate::{api_request, ApiParams, PaprikaError, Result};
+use serde_json::Value;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot};
+
+/// One caller's pending request: the endpoint/params to dispatch and the
+/// channel its individual result is returned on.
+struct QueuedRequest {
+    endpoint: String,
+    params: Option<ApiParams>,
+    respond_to: oneshot::Sender<Result<Value>>,
+}
+
+/// Tunes how `RequestScheduler` debounces and sizes batches.
+#[derive(Debug, Clone)]
+pub struct SchedulerConfig {
+    /// How long the worker waits after a request arrives before draining the
+    /// queue, so requests arriving in that window get grouped together.
+    pub debounce_duration: Duration,
+    /// Maximum number of requests accumulated into one debounce window
+    /// before the worker stops waiting and dispatches early.
+    pub max_batch_size: usize,
+    /// Maximum number of requests dispatched in a single concurrent flight.
+    /// A batch larger than this is split into multiple flights, but a flight
+    /// always contains at least one request.
+    pub max_requests_per_batch: usize,
+}
+
+impl Default for SchedulerConfig {
+    fn default() -> Self {
+        Self {
+            debounce_duration: Duration::from_millis(50),
+            max_batch_size: 50,
+            max_requests_per_batch: 10,
+        }
+    }
+}
+
+/// Builder for `RequestScheduler`.
+#[derive(Debug, Clone, Default)]
+pub struct RequestSchedulerBuilder {
+    config: SchedulerConfig,
+}
+
+impl RequestSchedulerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn debounce_duration(mut self, debounce_duration: Duration) -> Self {
+        self.config.debounce_duration = debounce_duration;
+        self
+    }
+
+    pub fn max_batch_size(mut self, max_batch_size: usize) -> Self {
+        self.config.max_batch_size = max_batch_size;
+        self
+    }
+
+    pub fn max_requests_per_batch(mut self, max_requests_per_batch: usize) -> Self {
+        self.config.max_requests_per_batch = max_requests_per_batch;
+        self
+    }
+
+    /// Builds the scheduler and spawns its background worker.
+    pub fn build(self) -> RequestScheduler {
+        RequestScheduler::with_config(self.config)
+    }
+}
+
+/// A debounced, auto-batching front-end for `api_request`. Cheap to clone —
+/// clones share the same background worker and queue.
+#[derive(Clone)]
+pub struct RequestScheduler {
+    sender: mpsc::UnboundedSender<QueuedRequest>,
+}
+
+impl RequestScheduler {
+    /// Builds a scheduler with default debounce/batch settings.
+    pub fn new() -> Self {
+        Self::with_config(SchedulerConfig::default())
+    }
+
+    pub fn with_config(config: SchedulerConfig) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(run_worker(receiver, config));
+        Self { sender }
+    }
+
+    pub fn builder() -> RequestSchedulerBuilder {
+        RequestSchedulerBuilder::new()
+    }
+
+    /// Enqueues an `api_request` call. Batching is invisible to the caller:
+    /// the returned future resolves to this request's own `Result` once the
+    /// batch it was grouped into has been dispatched.
+    pub async fn schedule(&self, endpoint: &str, params: Option<ApiParams>) -> Result<Value> {
+        let (respond_to, receiver) = oneshot::channel();
+        self.sender
+            .send(QueuedRequest {
+                endpoint: endpoint.to_string(),
+                params,
+                respond_to,
+            })
+            .map_err(|_| PaprikaError::GenericError("request scheduler worker has stopped".to_string()))?;
+
+        receiver
+            .await
+            .map_err(|_| PaprikaError::GenericError("request scheduler dropped the response".to_string()))?
+    }
+}
+
+impl Default for RequestScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+async fn run_worker(mut receiver: mpsc::UnboundedReceiver<QueuedRequest>, config: SchedulerConfig) {
+    loop {
+        let first = match receiver.recv().await {
+            Some(request) => request,
+            None => break,
+        };
+        let mut batch = vec![first];
+
+        let debounce = tokio::time::sleep(config.debounce_duration);
+        tokio::pin!(debounce);
+
+        loop {
+            if batch.len() >= config.max_batch_size {
+                break;
+            }
+            tokio::select! {
+                _ = &mut debounce => break,
+                next = receiver.recv() => match next {
+                    Some(request) => batch.push(request),
+                    None => break,
+                },
+            }
+        }
+
+        // Dispatch this batch as its own concurrent flight without waiting
+        // for it to finish, so the next debounce window can start draining
+        // immediately.
+        tokio::spawn(dispatch_batch(batch, config.max_requests_per_batch.max(1)));
+    }
+}
+
+async fn dispatch_batch(batch: Vec<QueuedRequest>, max_requests_per_batch: usize) {
+    let mut remaining = batch.into_iter();
+    loop {
+        let chunk: Vec<QueuedRequest> = remaining.by_ref().take(max_requests_per_batch.max(1)).collect();
+        if chunk.is_empty() {
+            break;
+        }
+
+        let flight = chunk.into_iter().map(|request| async move {
+            let result = api_request(&request.endpoint, request.params).await;
+            let _ = request.respond_to.send(result);
+        });
+        futures::future::join_all(flight).await;
+    }
+}
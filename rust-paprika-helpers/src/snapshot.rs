@@ -0,0 +1,267 @@
+//! Verifiable market-data snapshots via an append-only binary Merkle tree
+//!
+//! `MarketSnapshot` builds a binary Merkle tree over a canonicalized
+//! serialization of each `Pool` as it's inserted. A consumer can be handed
+//! one pool plus an `inclusion_proof` and confirm it against a trusted
+//! `root` without needing the rest of the snapshot.
+//!
+//! Leaves are `H(0x00 || canonical_bytes)`, internal nodes are
+//! `H(0x01 || left || right)`; a level with an odd node count promotes its
+//! last node unchanged to the next level rather than duplicating it. The
+//! tree supports only insertion, matching an immutable snapshot model.
+
+use crate::{PaprikaError, Pool, Result, TimeIntervalMetrics, Token};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashMap};
+
+const LEAF_PREFIX: u8 = 0x00;
+const NODE_PREFIX: u8 = 0x01;
+
+/// A SHA-256 digest, used for both leaf and internal node hashes.
+pub type Hash = [u8; 32];
+
+fn hash_leaf(canonical_bytes: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(canonical_bytes);
+    hasher.finalize().into()
+}
+
+fn hash_node(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Renders an `Option<T>` field as `"null"` or its formatted value, so a
+/// field going from `None` to `Some` (or vice versa) always changes the
+/// canonical bytes.
+fn canonicalize_opt<T>(value: &Option<T>, format: impl FnOnce(&T) -> String) -> String {
+    value.as_ref().map(format).unwrap_or_else(|| "null".to_string())
+}
+
+/// Canonicalizes one `h24`/`h6`/`h1`/`m30`/`m15`/`m5` window into the same
+/// sorted-field, fixed-precision representation as `canonicalize_pool`.
+fn canonicalize_window(metrics: &TimeIntervalMetrics) -> String {
+    let mut fields: BTreeMap<&str, String> = BTreeMap::new();
+    fields.insert("buy_usd", format!("{:.6}", metrics.buy_usd));
+    fields.insert("buys", metrics.buys.to_string());
+    fields.insert("last_price_usd_change", format!("{:.6}", metrics.last_price_usd_change));
+    fields.insert("sell_usd", format!("{:.6}", metrics.sell_usd));
+    fields.insert("sells", metrics.sells.to_string());
+    fields.insert("txns", metrics.txns.to_string());
+    fields.insert("volume", format!("{:.6}", metrics.volume));
+    fields.insert("volume_usd", format!("{:.6}", metrics.volume_usd));
+
+    fields.into_iter().map(|(key, value)| format!("{key}={value}")).collect::<Vec<_>>().join(",")
+}
+
+/// Canonicalizes a `Token`'s own fields (not its nested `summary`, which is
+/// enrichment data rather than identity) into the same sorted representation.
+fn canonicalize_token(token: &Token) -> String {
+    let mut fields: BTreeMap<&str, String> = BTreeMap::new();
+    fields.insert("added_at", token.added_at.clone());
+    fields.insert("chain", token.chain.clone());
+    fields.insert("decimals", token.decimals.to_string());
+    fields.insert("description", token.description.clone());
+    fields.insert("explorer", token.explorer.clone());
+    fields.insert("fdv", format!("{:.6}", token.fdv));
+    fields.insert("id", token.id.clone());
+    fields.insert("last_updated", token.last_updated.clone());
+    fields.insert("name", token.name.clone());
+    fields.insert("status", token.status.clone());
+    fields.insert("symbol", token.symbol.clone());
+    fields.insert("total_supply", format!("{:.6}", token.total_supply));
+    fields.insert("type", token.token_type.clone());
+    fields.insert("website", token.website.clone());
+
+    fields.into_iter().map(|(key, value)| format!("{key}={value}")).collect::<Vec<_>>().join(",")
+}
+
+/// Canonicalizes a `Pool` into a deterministic byte representation: every
+/// field sorted by name (via `BTreeMap`), numbers formatted to a fixed 6
+/// decimal places, `tokens` and the `h24`/`h6`/`h1`/`m30`/`m15`/`m5` windows
+/// canonicalized the same way and joined deterministically, so two
+/// logically-equal pools always hash identically regardless of struct field
+/// order or float-formatting quirks — and so a change to any field, not just
+/// the handful easiest to reach, changes the hash.
+fn canonicalize_pool(pool: &Pool) -> Vec<u8> {
+    let mut fields: BTreeMap<&str, String> = BTreeMap::new();
+    fields.insert("chain", pool.chain.clone());
+    fields.insert("created_at", pool.created_at.clone());
+    fields.insert("created_at_block_number", pool.created_at_block_number.to_string());
+    fields.insert("dex_id", pool.dex_id.clone());
+    fields.insert("dex_name", pool.dex_name.clone());
+    fields.insert("fee", canonicalize_opt(&pool.fee, |v| format!("{v:.6}")));
+    fields.insert("h1", canonicalize_opt(&pool.h1, canonicalize_window));
+    fields.insert("h24", canonicalize_opt(&pool.h24, canonicalize_window));
+    fields.insert("h6", canonicalize_opt(&pool.h6, canonicalize_window));
+    fields.insert("id", pool.id.clone());
+    fields.insert("last_price", canonicalize_opt(&pool.last_price, |v| format!("{v:.6}")));
+    fields.insert("last_price_change_usd_1h", format!("{:.6}", pool.last_price_change_usd_1h));
+    fields.insert("last_price_change_usd_24h", format!("{:.6}", pool.last_price_change_usd_24h));
+    fields.insert("last_price_change_usd_5m", format!("{:.6}", pool.last_price_change_usd_5m));
+    fields.insert("last_price_usd", canonicalize_opt(&pool.last_price_usd, |v| format!("{v:.6}")));
+    fields.insert("m15", canonicalize_opt(&pool.m15, canonicalize_window));
+    fields.insert("m30", canonicalize_opt(&pool.m30, canonicalize_window));
+    fields.insert("m5", canonicalize_opt(&pool.m5, canonicalize_window));
+    fields.insert("price_time", canonicalize_opt(&pool.price_time, Clone::clone));
+    fields.insert("price_usd", format!("{:.6}", pool.price_usd));
+    fields.insert(
+        "tokens",
+        pool.tokens.iter().map(canonicalize_token).collect::<Vec<_>>().join(";"),
+    );
+    fields.insert("transactions", pool.transactions.to_string());
+    fields.insert("volume_usd", format!("{:.6}", pool.volume_usd));
+
+    let mut buf = Vec::new();
+    for (key, value) in fields {
+        buf.extend_from_slice(key.as_bytes());
+        buf.push(b'=');
+        buf.extend_from_slice(value.as_bytes());
+        buf.push(b'\n');
+    }
+    buf
+}
+
+/// A sibling path from a leaf up to the root, bottom-up.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleProof {
+    /// Sibling hash at each level climbed, in order from the leaf's level
+    /// upward.
+    pub siblings: Vec<Hash>,
+    /// Whether the corresponding `siblings` entry sits to the left (`true`)
+    /// or right (`false`) of the node being hashed up at that level.
+    pub sibling_is_left: Vec<bool>,
+    /// The leaf's index at insertion time.
+    pub leaf_index: usize,
+}
+
+/// An append-only binary Merkle tree over `Pool` leaves.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleTree {
+    leaves: Vec<Hash>,
+    index_by_pool_id: HashMap<String, usize>,
+}
+
+impl MerkleTree {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `pool` as the next leaf. The tree supports only insertion —
+    /// there's no way to remove or replace a leaf once added.
+    pub fn insert(&mut self, pool: &Pool) {
+        let leaf = hash_leaf(&canonicalize_pool(pool));
+        self.index_by_pool_id.insert(pool.id.clone(), self.leaves.len());
+        self.leaves.push(leaf);
+    }
+
+    /// Builds every level of the tree bottom-up from the current leaves. A
+    /// level with an odd node count promotes its last node unchanged to the
+    /// next level rather than duplicating it.
+    fn levels(&self) -> Vec<Vec<Hash>> {
+        let mut levels = vec![self.leaves.clone()];
+
+        while levels.last().is_some_and(|level| level.len() > 1) {
+            let current = levels.last().expect("checked non-empty above");
+            let mut next = Vec::with_capacity(current.len().div_ceil(2));
+            let mut i = 0;
+
+            while i + 1 < current.len() {
+                next.push(hash_node(&current[i], &current[i + 1]));
+                i += 2;
+            }
+            if i < current.len() {
+                next.push(current[i]);
+            }
+
+            levels.push(next);
+        }
+
+        levels
+    }
+
+    /// The tree's current root, or `None` if no leaves have been inserted.
+    pub fn root(&self) -> Option<Hash> {
+        self.levels().last().and_then(|top| top.first().copied())
+    }
+
+    /// Builds an inclusion proof for the pool with the given `pool_id`, or
+    /// `None` if no such pool was ever inserted.
+    pub fn inclusion_proof(&self, pool_id: &str) -> Option<MerkleProof> {
+        let &leaf_index = self.index_by_pool_id.get(pool_id)?;
+        let levels = self.levels();
+
+        let mut index = leaf_index;
+        let mut siblings = Vec::new();
+        let mut sibling_is_left = Vec::new();
+
+        for level in &levels[..levels.len().saturating_sub(1)] {
+            if index % 2 == 0 {
+                if let Some(&sibling) = level.get(index + 1) {
+                    siblings.push(sibling);
+                    sibling_is_left.push(false);
+                }
+                // Odd node count at this level: `index` was promoted
+                // unchanged, so there's no sibling to prove against here.
+            } else {
+                siblings.push(level[index - 1]);
+                sibling_is_left.push(true);
+            }
+            index /= 2;
+        }
+
+        Some(MerkleProof { siblings, sibling_is_left, leaf_index })
+    }
+}
+
+/// Recomputes `pool`'s leaf hash and climbs `proof`'s sibling path, returning
+/// whether the result matches `root`.
+pub fn verify(root: Hash, pool: &Pool, proof: &MerkleProof) -> bool {
+    let mut current = hash_leaf(&canonicalize_pool(pool));
+
+    for (sibling, is_left) in proof.siblings.iter().zip(&proof.sibling_is_left) {
+        current = if *is_left { hash_node(sibling, &current) } else { hash_node(&current, sibling) };
+    }
+
+    current == root
+}
+
+/// A point-in-time, verifiable snapshot of a set of pools.
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    pub root: Hash,
+    pub taken_at: DateTime<Utc>,
+    pub tree: MerkleTree,
+}
+
+impl MarketSnapshot {
+    /// Builds a snapshot over `pools`, inserting each into a fresh tree in
+    /// order.
+    pub fn build(pools: &[Pool], taken_at: DateTime<Utc>) -> Result<Self> {
+        let mut tree = MerkleTree::new();
+        for pool in pools {
+            tree.insert(pool);
+        }
+
+        let root = tree
+            .root()
+            .ok_or_else(|| PaprikaError::ValidationError("cannot snapshot an empty pool set".to_string()))?;
+
+        Ok(Self { root, taken_at, tree })
+    }
+
+    /// Builds an inclusion proof for `pool_id` against this snapshot's tree.
+    pub fn inclusion_proof(&self, pool_id: &str) -> Option<MerkleProof> {
+        self.tree.inclusion_proof(pool_id)
+    }
+
+    /// Verifies `pool` plus `proof` against this snapshot's `root`.
+    pub fn verify(&self, pool: &Pool, proof: &MerkleProof) -> bool {
+        verify(self.root, pool, proof)
+    }
+}
@@ -0,0 +1,237 @@
+//! Postgres persistence layer
+//!
+//! A durable sink alongside `save_to_csv`: persists `Pool`/`Token` rows and
+//! derived OHLCV candle rows with an upsert on their natural keys, so
+//! re-running a backfill is idempotent. Raw pool/token rows and derived
+//! candle rows live in separate tables, so recomputing candles never
+//! requires re-fetching the underlying data.
+
+use crate::{backfill_pool_ohlcv, get_network_pools, ApiParams, OHLCVRecord, Pool, Result, Token};
+use chrono::{DateTime, Utc};
+use tokio_postgres::{Client, NoTls};
+
+/// A Postgres-backed sink for pools, tokens, and OHLCV candles.
+pub struct Storage {
+    client: Client,
+}
+
+impl Storage {
+    /// Connects to `url` and ensures the backing tables exist.
+    pub async fn connect(url: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(url, NoTls)
+            .await
+            .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                eprintln!("storage connection error: {}", e);
+            }
+        });
+
+        let storage = Self { client };
+        storage.ensure_schema().await?;
+        Ok(storage)
+    }
+
+    async fn ensure_schema(&self) -> Result<()> {
+        self.client
+            .batch_execute(
+                "CREATE TABLE IF NOT EXISTS pools (
+                    id TEXT PRIMARY KEY,
+                    dex_name TEXT NOT NULL,
+                    chain TEXT NOT NULL,
+                    volume_usd DOUBLE PRECISION NOT NULL,
+                    price_usd DOUBLE PRECISION NOT NULL,
+                    last_price_change_usd_24h DOUBLE PRECISION NOT NULL,
+                    updated_at TIMESTAMPTZ NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS tokens (
+                    id TEXT PRIMARY KEY,
+                    chain TEXT NOT NULL,
+                    name TEXT NOT NULL,
+                    symbol TEXT NOT NULL,
+                    updated_at TIMESTAMPTZ NOT NULL
+                );
+                CREATE TABLE IF NOT EXISTS ohlcv_candles (
+                    pool_id TEXT NOT NULL,
+                    time_open TIMESTAMPTZ NOT NULL,
+                    open DOUBLE PRECISION NOT NULL,
+                    high DOUBLE PRECISION NOT NULL,
+                    low DOUBLE PRECISION NOT NULL,
+                    close DOUBLE PRECISION NOT NULL,
+                    volume BIGINT NOT NULL,
+                    PRIMARY KEY (pool_id, time_open)
+                );
+                CREATE TABLE IF NOT EXISTS backfill_progress (
+                    network TEXT PRIMARY KEY,
+                    watermark TIMESTAMPTZ NOT NULL
+                );",
+            )
+            .await
+            .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Upserts pools keyed by `id`.
+    pub async fn upsert_pools(&self, pools: &[Pool]) -> Result<()> {
+        for pool in pools {
+            self.client
+                .execute(
+                    "INSERT INTO pools (id, dex_name, chain, volume_usd, price_usd, last_price_change_usd_24h, updated_at)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7)
+                     ON CONFLICT (id) DO UPDATE SET
+                        dex_name = EXCLUDED.dex_name,
+                        chain = EXCLUDED.chain,
+                        volume_usd = EXCLUDED.volume_usd,
+                        price_usd = EXCLUDED.price_usd,
+                        last_price_change_usd_24h = EXCLUDED.last_price_change_usd_24h,
+                        updated_at = EXCLUDED.updated_at",
+                    &[
+                        &pool.id,
+                        &pool.dex_name,
+                        &pool.chain,
+                        &pool.volume_usd,
+                        &pool.price_usd,
+                        &pool.last_price_change_usd_24h,
+                        &Utc::now(),
+                    ],
+                )
+                .await
+                .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Upserts tokens keyed by `id`.
+    pub async fn upsert_tokens(&self, tokens: &[Token]) -> Result<()> {
+        for token in tokens {
+            self.client
+                .execute(
+                    "INSERT INTO tokens (id, chain, name, symbol, updated_at)
+                     VALUES ($1, $2, $3, $4, $5)
+                     ON CONFLICT (id) DO UPDATE SET
+                        chain = EXCLUDED.chain,
+                        name = EXCLUDED.name,
+                        symbol = EXCLUDED.symbol,
+                        updated_at = EXCLUDED.updated_at",
+                    &[&token.id, &token.chain, &token.name, &token.symbol, &Utc::now()],
+                )
+                .await
+                .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Upserts derived OHLCV candles for `pool_id`, keyed on `(pool_id, time_open)`.
+    pub async fn upsert_candles(&self, pool_id: &str, candles: &[OHLCVRecord]) -> Result<()> {
+        for candle in candles {
+            let time_open = DateTime::parse_from_rfc3339(&candle.time_open)
+                .map(|ts| ts.with_timezone(&Utc))
+                .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+
+            self.client
+                .execute(
+                    "INSERT INTO ohlcv_candles (pool_id, time_open, open, high, low, close, volume)
+                     VALUES ($1, $2, $3, $4, $5, $6, $7)
+                     ON CONFLICT (pool_id, time_open) DO UPDATE SET
+                        open = EXCLUDED.open,
+                        high = EXCLUDED.high,
+                        low = EXCLUDED.low,
+                        close = EXCLUDED.close,
+                        volume = EXCLUDED.volume",
+                    &[
+                        &pool_id,
+                        &time_open,
+                        &candle.open,
+                        &candle.high,
+                        &candle.low,
+                        &candle.close,
+                        &candle.volume,
+                    ],
+                )
+                .await
+                .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Returns the last persisted watermark for `network`, if any.
+    pub async fn watermark(&self, network: &str) -> Result<Option<DateTime<Utc>>> {
+        let row = self
+            .client
+            .query_opt(
+                "SELECT watermark FROM backfill_progress WHERE network = $1",
+                &[&network],
+            )
+            .await
+            .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+        Ok(row.map(|r| r.get("watermark")))
+    }
+
+    async fn set_watermark(&self, network: &str, watermark: DateTime<Utc>) -> Result<()> {
+        self.client
+            .execute(
+                "INSERT INTO backfill_progress (network, watermark)
+                 VALUES ($1, $2)
+                 ON CONFLICT (network) DO UPDATE SET watermark = EXCLUDED.watermark",
+                &[&network, &watermark],
+            )
+            .await
+            .map_err(|e| crate::PaprikaError::GenericError(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Walks `network`'s pools page by page, persisting each page and
+    /// backfilling `resolution` OHLCV candles for `[resume_point, to]` for
+    /// every pool on that page via `backfill_pool_ohlcv`. The watermark is
+    /// only advanced to `to` once every page has been walked — recording it
+    /// any earlier (e.g. wall-clock time after each page) would make a
+    /// resumed call's `resume_point` land at or past `to` even though most of
+    /// the range was never backfilled, silently turning "resume" into a
+    /// no-op. With no watermark yet the backfill starts at `from`; otherwise
+    /// it starts at the later of `from` and the last persisted watermark.
+    pub async fn backfill(
+        &self,
+        network: &str,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+        resolution: &str,
+    ) -> Result<()> {
+        let resume_point = backfill_resume_point(self.watermark(network).await?, from);
+        if resume_point >= to {
+            return Ok(());
+        }
+
+        let mut page = 1;
+        loop {
+            let response = get_network_pools(network, Some(ApiParams::new().page(page).limit(100))).await?;
+            if response.pools.is_empty() {
+                break;
+            }
+
+            self.upsert_pools(&response.pools).await?;
+
+            for pool in &response.pools {
+                let candles =
+                    backfill_pool_ohlcv(network, &pool.id, resume_point, to, resolution, None, |_| {}).await?;
+                self.upsert_candles(&pool.id, &candles).await?;
+            }
+
+            if page >= response.page_info.total_pages {
+                break;
+            }
+            page += 1;
+        }
+
+        self.set_watermark(network, to).await?;
+        Ok(())
+    }
+}
+
+/// Resume point for a historical `[from, to]` backfill: the later of the last
+/// persisted watermark and `from`, so a later `from` isn't overridden by a
+/// stale watermark, but a prior completed run's watermark prevents redoing
+/// already-backfilled history.
+pub fn backfill_resume_point(watermark: Option<DateTime<Utc>>, from: DateTime<Utc>) -> DateTime<Utc> {
+    watermark.map(|w| w.max(from)).unwrap_or(from)
+}
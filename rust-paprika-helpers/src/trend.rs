@@ -0,0 +1,185 @@
+//! Sliding-window trend aggregation over monitored pools
+//!
+//! Layers on top of `async_monitor_prices`: each price tick is routed into
+//! `TrendTracker`, which keeps a `VecDeque<Bucket>` per pool bucketed by
+//! `bucket_duration` (5 minutes by default) and rolled off past `horizon`
+//! (24h by default). `top_trending` ranks the tracked pools by percentage
+//! price change or volume surge over that window, so a "trending pools"
+//! view doesn't need to externally re-aggregate the monitor's callback
+//! stream.
+
+use chrono::{DateTime, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, OnceLock};
+use std::time::Duration;
+
+/// One time-bucketed rolling aggregate of a pool's price/volume.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bucket {
+    pub start_ts: i64,
+    pub price_open: f64,
+    pub price_close: f64,
+    pub volume_sum: f64,
+    pub sample_count: u32,
+}
+
+/// Which score `top_trending` ranks pools by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrendMetric {
+    /// Percentage change from the oldest bucket's open to the newest
+    /// bucket's close.
+    PercentChange,
+    /// Sum of `volume_sum` across all tracked buckets.
+    VolumeSurge,
+}
+
+/// A pool's rank under a `TrendMetric`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrendingPool {
+    pub pool_address: String,
+    pub score: f64,
+}
+
+/// Maintains a rolling, bucketed price/volume history per pool and ranks
+/// them by recent movement.
+pub struct TrendTracker {
+    buckets: HashMap<String, VecDeque<Bucket>>,
+    bucket_duration_secs: i64,
+    horizon_secs: i64,
+}
+
+impl TrendTracker {
+    /// Builds a tracker with `bucket_duration`-wide buckets over a sliding
+    /// `horizon`.
+    pub fn new(bucket_duration: Duration, horizon: Duration) -> Self {
+        Self {
+            buckets: HashMap::new(),
+            bucket_duration_secs: bucket_duration.as_secs().max(1) as i64,
+            horizon_secs: horizon.as_secs() as i64,
+        }
+    }
+}
+
+impl Default for TrendTracker {
+    /// 5-minute buckets over a 24h horizon, matching the monitor's typical
+    /// polling cadence.
+    fn default() -> Self {
+        Self::new(Duration::from_secs(300), Duration::from_secs(24 * 60 * 60))
+    }
+}
+
+impl TrendTracker {
+    /// Routes one price/volume sample for `pool_address` into its current
+    /// bucket, filling any gap buckets since the last sample so polling
+    /// gaps still advance time, then rolls off buckets older than the
+    /// horizon.
+    pub fn record(&mut self, pool_address: &str, price_usd: f64, volume_usd: f64, timestamp: DateTime<Utc>) {
+        let bucket_start = (timestamp.timestamp() / self.bucket_duration_secs) * self.bucket_duration_secs;
+        let buckets = self.buckets.entry(pool_address.to_string()).or_default();
+
+        match buckets.back_mut() {
+            Some(last) if last.start_ts == bucket_start => {
+                last.price_close = price_usd;
+                last.volume_sum += volume_usd;
+                last.sample_count += 1;
+            }
+            Some(last) => {
+                let carry_close = last.price_close;
+                let mut gap_start = last.start_ts + self.bucket_duration_secs;
+                while gap_start < bucket_start {
+                    buckets.push_back(Bucket {
+                        start_ts: gap_start,
+                        price_open: carry_close,
+                        price_close: carry_close,
+                        volume_sum: 0.0,
+                        sample_count: 0,
+                    });
+                    gap_start += self.bucket_duration_secs;
+                }
+                buckets.push_back(Bucket {
+                    start_ts: bucket_start,
+                    price_open: price_usd,
+                    price_close: price_usd,
+                    volume_sum: volume_usd,
+                    sample_count: 1,
+                });
+            }
+            None => {
+                buckets.push_back(Bucket {
+                    start_ts: bucket_start,
+                    price_open: price_usd,
+                    price_close: price_usd,
+                    volume_sum: volume_usd,
+                    sample_count: 1,
+                });
+            }
+        }
+
+        let cutoff = bucket_start - self.horizon_secs;
+        while buckets.front().is_some_and(|b| b.start_ts < cutoff) {
+            buckets.pop_front();
+        }
+
+        self.evict_stale(timestamp);
+    }
+
+    /// Drops pools whose newest bucket has aged out of the horizon — i.e.
+    /// pools that have dropped out of the monitored set entirely, rather
+    /// than just gone quiet within the window.
+    pub fn evict_stale(&mut self, now: DateTime<Utc>) {
+        let now_ts = now.timestamp();
+        self.buckets
+            .retain(|_, buckets| buckets.back().is_some_and(|b| now_ts - b.start_ts < self.horizon_secs));
+    }
+
+    /// Returns the top `n` pools ranked by `metric`, highest score first.
+    pub fn top_trending(&self, n: usize, metric: TrendMetric) -> Vec<TrendingPool> {
+        let mut ranked: Vec<TrendingPool> = self
+            .buckets
+            .iter()
+            .filter_map(|(pool_address, buckets)| {
+                let first = buckets.front()?;
+                let last = buckets.back()?;
+
+                let score = match metric {
+                    TrendMetric::PercentChange => {
+                        if first.price_open == 0.0 {
+                            0.0
+                        } else {
+                            ((last.price_close - first.price_open) / first.price_open) * 100.0
+                        }
+                    }
+                    TrendMetric::VolumeSurge => buckets.iter().map(|b| b.volume_sum).sum(),
+                };
+
+                Some(TrendingPool { pool_address: pool_address.clone(), score })
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+        ranked.truncate(n);
+        ranked
+    }
+}
+
+static TREND_TRACKER: OnceLock<Mutex<TrendTracker>> = OnceLock::new();
+
+fn get_trend_tracker() -> &'static Mutex<TrendTracker> {
+    TREND_TRACKER.get_or_init(|| Mutex::new(TrendTracker::default()))
+}
+
+/// Records one price/volume sample into the shared tracker backing
+/// `top_trending`. Called automatically on every `async_monitor_prices`
+/// tick; exposed directly for callers driving their own polling loop.
+pub fn record_trend_sample(pool_address: &str, price_usd: f64, volume_usd: f64, timestamp: DateTime<Utc>) {
+    get_trend_tracker()
+        .lock()
+        .expect("trend tracker mutex poisoned")
+        .record(pool_address, price_usd, volume_usd, timestamp);
+}
+
+/// Returns the top `n` monitored pools ranked by `metric`, drawn from the
+/// shared tracker `async_monitor_prices` feeds on every tick.
+pub fn top_trending(n: usize, metric: TrendMetric) -> Vec<TrendingPool> {
+    get_trend_tracker().lock().expect("trend tracker mutex poisoned").top_trending(n, metric)
+}